@@ -1,21 +1,57 @@
 // filepath: /Users/kevinjacob/Developer/projects/liquity-locking-program/programs/liqudity-locking-program/src/constants.rs
 use anchor_lang::prelude::*;
 
-/// SLERF-USDC Pool Address (hardcoded for this program)
-pub const SLERF_USDC_POOL: Pubkey = pubkey!("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
-
 /// Meteora DAMM Program ID
 pub const METEORA_PROGRAM_ID: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
 
 /// Pool Authority Address (fixed from IDL)
 pub const POOL_AUTHORITY: Pubkey = pubkey!("HLnpSz9h2S4hiLQ43rnSD9XkcUThA7B8hQMKmDaiTLcC");
 
+/// Basis points denominator (100%)
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Maximum protocol fee (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Maximum tier reward multiplier (5x)
+pub const MAX_MULTIPLIER_BPS: u16 = 50_000;
+
+/// Maximum number of duration tiers per pool
+pub const MAX_DURATION_TIERS: usize = 8;
+
+/// Tier index recorded for locks created with a raw duration in seconds
+pub const CUSTOM_DURATION_TIER: u8 = u8::MAX;
+
+/// Period length of native Meteora vesting schedules (one day)
+pub const VESTING_PERIOD_SECONDS: u64 = 86_400;
+
+/// Approximate slot duration, for pools activated by slot
+pub const SLOT_DURATION_MS: u64 = 400;
+
+/// Fractional bits of the U256 per-liquidity accumulators. DAMM v2 liquidity already carries
+/// a 2^64 factor, so a 128-bit scale (as DAMM v2's fee accumulators use) keeps small amounts
+/// spread over large liquidity from rounding to zero
+pub const ACCUMULATOR_SCALE_OFFSET: u32 = 128;
+
+/// DAMM v2 pool_status of an enabled pool (1 = disabled)
+pub const POOL_STATUS_ENABLED: u8 = 0;
+
+/// Token metadata of lock receipt NFTs
+pub const RECEIPT_NAME: &str = "Liquidity Lock Receipt";
+pub const RECEIPT_SYMBOL: &str = "LOCK";
+pub const RECEIPT_URI: &str = "";
+
 pub mod seeds {
     pub const POSITION_NFT_MINT_SEED: &[u8] = b"position_nft_mint";
     pub const POSITION_SEED: &[u8] = b"position";
     pub const VESTING_SEED: &[u8] = b"vesting";
     pub const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
-    pub const LOCK_SEED: &[u8] = b"lock";                          // For LockAccount PDA: ["lock", user_pubkey, position_nft_mint]
+    pub const LOCK_SEED: &[u8] = b"lock";                          // For LockAccount PDA: ["lock", position_nft_mint]
     pub const CONFIG_SEED: &[u8] = b"config";                      // For Config PDA: ["config"]
-    pub const ESCROW_AUTHORITY_SEED: &[u8] = b"escrow_authority";  // For EscrowAuthority PDA: ["escrow_authority"]
+    pub const ESCROW_AUTHORITY_SEED: &[u8] = b"escrow_authority";  // For EscrowAuthority PDA: ["escrow_authority", lock_account]
+    pub const POOL_CONFIG_SEED: &[u8] = b"pool_config";            // For PoolConfig PDA: ["pool_config", pool]
+    pub const LOCK_VESTING_SEED: &[u8] = b"lock_vesting";          // For Meteora vesting account of a lock: ["lock_vesting", lock_account]
+    pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";          // For lock receipt NFT mint: ["receipt_mint", lock_account]
+    pub const PENALTY_VAULT_SEED: &[u8] = b"penalty_vault";        // For redistributed penalty vaults: ["penalty_vault", pool_config, mint]
+    pub const INCENTIVE_VAULT_SEED: &[u8] = b"incentive_vault";    // For program incentive vaults: ["incentive_vault", pool_config]
 }
\ No newline at end of file
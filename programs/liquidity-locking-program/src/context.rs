@@ -63,6 +63,58 @@ pub mod damm_v2 {
                 pub program: AccountInfo<'info>,
             }
 
+            #[derive(Accounts)]
+            pub struct RefreshVesting<'info> {
+                /// CHECK: Pool account, readonly for Meteora refresh_vesting
+                pub pool: AccountInfo<'info>,
+                /// CHECK: Position account, writable for Meteora refresh_vesting
+                pub position: AccountInfo<'info>,
+                /// CHECK: Position NFT account, readonly for Meteora refresh_vesting
+                pub position_nft_account: AccountInfo<'info>,
+                /// CHECK: Owner, readonly for Meteora refresh_vesting
+                pub owner: AccountInfo<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct PermanentLockPosition<'info> {
+                /// CHECK: Pool account, writable for Meteora permanent_lock_position
+                pub pool: AccountInfo<'info>,
+                /// CHECK: Position account, writable for Meteora permanent_lock_position
+                pub position: AccountInfo<'info>,
+                /// CHECK: Position NFT account, readonly for Meteora permanent_lock_position
+                pub position_nft_account: AccountInfo<'info>,
+                /// CHECK: Owner, signer for Meteora permanent_lock_position
+                pub owner: AccountInfo<'info>,
+                /// CHECK: Event authority, readonly for Meteora permanent_lock_position
+                pub event_authority: AccountInfo<'info>,
+                /// CHECK: Meteora program, readonly
+                pub program: AccountInfo<'info>,
+            }
+
+            #[derive(Accounts)]
+            pub struct ClosePosition<'info> {
+                /// CHECK: Position NFT mint, writable for Meteora close_position
+                pub position_nft_mint: AccountInfo<'info>,
+                /// CHECK: Position NFT account, writable for Meteora close_position
+                pub position_nft_account: AccountInfo<'info>,
+                /// CHECK: Pool account, writable for Meteora close_position
+                pub pool: AccountInfo<'info>,
+                /// CHECK: Position account, writable for Meteora close_position
+                pub position: AccountInfo<'info>,
+                /// CHECK: Pool authority, readonly for Meteora close_position
+                pub pool_authority: AccountInfo<'info>,
+                /// CHECK: Rent receiver, writable for Meteora close_position
+                pub rent_receiver: AccountInfo<'info>,
+                /// CHECK: Owner, signer for Meteora close_position
+                pub owner: AccountInfo<'info>,
+                /// CHECK: Token program, readonly for Meteora close_position
+                pub token_program: AccountInfo<'info>,
+                /// CHECK: Event authority, readonly for Meteora close_position
+                pub event_authority: AccountInfo<'info>,
+                /// CHECK: Meteora program, readonly
+                pub program: AccountInfo<'info>,
+            }
+
             #[derive(Accounts)]
             pub struct AddLiquidity<'info> {
                 /// CHECK: Pool account, writable for Meteora add_liquidity
@@ -97,6 +149,8 @@ pub mod damm_v2 {
 
             #[derive(Accounts)]
             pub struct RemoveLiquidity<'info> {
+                /// CHECK: Pool authority PDA, readonly for Meteora remove_liquidity
+                pub pool_authority: AccountInfo<'info>,           // fixed
                 /// CHECK: Pool account, writable for Meteora remove_liquidity
                 pub pool: AccountInfo<'info>,                     // writable
                 /// CHECK: Position account, writable for Meteora remove_liquidity
@@ -129,6 +183,8 @@ pub mod damm_v2 {
 
             #[derive(Accounts)]
             pub struct RemoveAllLiquidity<'info> {
+                /// CHECK: Pool authority PDA, readonly for Meteora remove_all_liquidity
+                pub pool_authority: AccountInfo<'info>,           // fixed
                 /// CHECK: Pool account, writable for Meteora remove_all_liquidity
                 pub pool: AccountInfo<'info>,                     // writable
                 /// CHECK: Position account, writable for Meteora remove_all_liquidity
@@ -161,6 +217,8 @@ pub mod damm_v2 {
 
             #[derive(Accounts)]
             pub struct ClaimReward<'info> {
+                /// CHECK: Pool authority PDA, readonly for Meteora claim_reward
+                pub pool_authority: AccountInfo<'info>,           // fixed
                 /// CHECK: Pool account, writable for Meteora claim_reward
                 pub pool: AccountInfo<'info>,                     // writable
                 /// CHECK: Position account, writable for Meteora claim_reward
@@ -185,6 +243,8 @@ pub mod damm_v2 {
 
             #[derive(Accounts)]
             pub struct ClaimPositionFee<'info> {
+                /// CHECK: Pool authority PDA, readonly for Meteora claim_position_fee
+                pub pool_authority: AccountInfo<'info>,           // fixed
                 /// CHECK: Pool account, readonly for Meteora claim_position_fee
                 pub pool: AccountInfo<'info>,                     // readonly
                 /// CHECK: Position account, writable for Meteora claim_position_fee
@@ -302,6 +362,120 @@ pub mod damm_v2 {
             Ok(())
         }
 
+        // CPI function for permanent_lock_position
+        pub fn permanent_lock_position<'info>(
+            ctx: CpiContext<'_, '_, '_, 'info, accounts::PermanentLockPosition<'info>>,
+            permanent_lock_liquidity: u128,
+        ) -> Result<()> {
+            let discriminator = [165, 176, 125, 6, 231, 171, 186, 213];
+            let mut data = discriminator.to_vec();
+            permanent_lock_liquidity.serialize(&mut data)?;
+
+            let ix = anchor_lang::solana_program::instruction::Instruction {
+                program_id: ID,
+                accounts: vec![
+                    AccountMeta::new(ctx.accounts.pool.key(), false),
+                    AccountMeta::new(ctx.accounts.position.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.position_nft_account.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.owner.key(), true),
+                    AccountMeta::new_readonly(ctx.accounts.event_authority.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.program.key(), false),
+                ],
+                data,
+            };
+            invoke_signed(
+                &ix,
+                &[
+                    ctx.accounts.pool.clone(),
+                    ctx.accounts.position.clone(),
+                    ctx.accounts.position_nft_account.clone(),
+                    ctx.accounts.owner.clone(),
+                    ctx.accounts.event_authority.clone(),
+                    ctx.accounts.program.clone(),
+                ],
+                ctx.signer_seeds,
+            )?;
+            Ok(())
+        }
+
+        // CPI function for refresh_vesting (vesting accounts passed as remaining accounts)
+        pub fn refresh_vesting<'info>(
+            ctx: CpiContext<'_, '_, '_, 'info, accounts::RefreshVesting<'info>>,
+        ) -> Result<()> {
+            let discriminator = [9, 94, 216, 14, 116, 204, 247, 0];
+            let data = discriminator.to_vec();
+
+            let mut accounts = vec![
+                AccountMeta::new_readonly(ctx.accounts.pool.key(), false),
+                AccountMeta::new(ctx.accounts.position.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.position_nft_account.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.owner.key(), false),
+            ];
+            accounts.extend(ctx.remaining_accounts.iter().map(|vesting| AccountMeta::new(vesting.key(), false)));
+
+            let mut account_infos = vec![
+                ctx.accounts.pool.clone(),
+                ctx.accounts.position.clone(),
+                ctx.accounts.position_nft_account.clone(),
+                ctx.accounts.owner.clone(),
+            ];
+            account_infos.extend(ctx.remaining_accounts.iter().cloned());
+
+            let ix = anchor_lang::solana_program::instruction::Instruction {
+                program_id: ID,
+                accounts,
+                data,
+            };
+            invoke_signed(
+                &ix,
+                &account_infos,
+                ctx.signer_seeds,
+            )?;
+            Ok(())
+        }
+
+        // CPI function for close_position
+        pub fn close_position<'info>(
+            ctx: CpiContext<'_, '_, '_, 'info, accounts::ClosePosition<'info>>,
+        ) -> Result<()> {
+            let discriminator = [123, 134, 81, 0, 49, 68, 98, 98];
+            let data = discriminator.to_vec();
+
+            let ix = anchor_lang::solana_program::instruction::Instruction {
+                program_id: ID,
+                accounts: vec![
+                    AccountMeta::new(ctx.accounts.position_nft_mint.key(), false),
+                    AccountMeta::new(ctx.accounts.position_nft_account.key(), false),
+                    AccountMeta::new(ctx.accounts.pool.key(), false),
+                    AccountMeta::new(ctx.accounts.position.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false),
+                    AccountMeta::new(ctx.accounts.rent_receiver.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.owner.key(), true),
+                    AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.event_authority.key(), false),
+                    AccountMeta::new_readonly(ctx.accounts.program.key(), false),
+                ],
+                data,
+            };
+            invoke_signed(
+                &ix,
+                &[
+                    ctx.accounts.position_nft_mint.clone(),
+                    ctx.accounts.position_nft_account.clone(),
+                    ctx.accounts.pool.clone(),
+                    ctx.accounts.position.clone(),
+                    ctx.accounts.pool_authority.clone(),
+                    ctx.accounts.rent_receiver.clone(),
+                    ctx.accounts.owner.clone(),
+                    ctx.accounts.token_program.clone(),
+                    ctx.accounts.event_authority.clone(),
+                    ctx.accounts.program.clone(),
+                ],
+                ctx.signer_seeds,
+            )?;
+            Ok(())
+        }
+
         // CPI function for add_liquidity
         pub fn add_liquidity<'info>(
             ctx: CpiContext<'_, '_, '_, 'info, accounts::AddLiquidity<'info>>,
@@ -349,7 +523,7 @@ pub mod damm_v2 {
                     ctx.accounts.event_authority.clone(),
                     ctx.accounts.program.clone(),
                 ],
-                &[]
+                ctx.signer_seeds,
             )?;
             Ok(())
         }
@@ -366,6 +540,7 @@ pub mod damm_v2 {
             let ix = anchor_lang::solana_program::instruction::Instruction {
                 program_id: ID,
                 accounts: vec![
+                    AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false),
                     AccountMeta::new(ctx.accounts.pool.key(), false),
                     AccountMeta::new(ctx.accounts.position.key(), false),
                     AccountMeta::new(ctx.accounts.token_a_account.key(), false),
@@ -386,6 +561,7 @@ pub mod damm_v2 {
             invoke_signed(
                 &ix,
                 &[
+                    ctx.accounts.pool_authority.clone(),
                     ctx.accounts.pool.clone(),
                     ctx.accounts.position.clone(),
                     ctx.accounts.token_a_account.clone(),
@@ -401,7 +577,7 @@ pub mod damm_v2 {
                     ctx.accounts.event_authority.clone(),
                     ctx.accounts.program.clone(),
                 ],
-                &[]
+                ctx.signer_seeds,
             )?;
             Ok(())
         }
@@ -420,6 +596,7 @@ pub mod damm_v2 {
             let ix = anchor_lang::solana_program::instruction::Instruction {
                 program_id: ID,
                 accounts: vec![
+                    AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false),
                     AccountMeta::new(ctx.accounts.pool.key(), false),
                     AccountMeta::new(ctx.accounts.position.key(), false),
                     AccountMeta::new(ctx.accounts.token_a_account.key(), false),
@@ -440,6 +617,7 @@ pub mod damm_v2 {
             invoke_signed(
                 &ix,
                 &[
+                    ctx.accounts.pool_authority.clone(),
                     ctx.accounts.pool.clone(),
                     ctx.accounts.position.clone(),
                     ctx.accounts.token_a_account.clone(),
@@ -455,7 +633,7 @@ pub mod damm_v2 {
                     ctx.accounts.event_authority.clone(),
                     ctx.accounts.program.clone(),
                 ],
-                &[]
+                ctx.signer_seeds,
             )?;
             Ok(())
         }
@@ -474,6 +652,7 @@ pub mod damm_v2 {
             let ix = anchor_lang::solana_program::instruction::Instruction {
                 program_id: ID,
                 accounts: vec![
+                    AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false),
                     AccountMeta::new(ctx.accounts.pool.key(), false),
                     AccountMeta::new(ctx.accounts.position.key(), false),
                     AccountMeta::new(ctx.accounts.reward_vault.key(), false),
@@ -490,6 +669,7 @@ pub mod damm_v2 {
             invoke_signed(
                 &ix,
                 &[
+                    ctx.accounts.pool_authority.clone(),
                     ctx.accounts.pool.clone(),
                     ctx.accounts.position.clone(),
                     ctx.accounts.reward_vault.clone(),
@@ -501,7 +681,7 @@ pub mod damm_v2 {
                     ctx.accounts.event_authority.clone(),
                     ctx.accounts.program.clone(),
                 ],
-                &[]
+                ctx.signer_seeds,  // Escrow authority signs as position owner
             )?;
             Ok(())
         }
@@ -516,6 +696,7 @@ pub mod damm_v2 {
             let ix = anchor_lang::solana_program::instruction::Instruction {
                 program_id: ID,
                 accounts: vec![
+                    AccountMeta::new_readonly(ctx.accounts.pool_authority.key(), false),
                     AccountMeta::new_readonly(ctx.accounts.pool.key(), false),
                     AccountMeta::new(ctx.accounts.position.key(), false),
                     AccountMeta::new(ctx.accounts.token_a_account.key(), false),
@@ -536,6 +717,7 @@ pub mod damm_v2 {
             invoke_signed(
                 &ix,
                 &[
+                    ctx.accounts.pool_authority.clone(),
                     ctx.accounts.pool.clone(),
                     ctx.accounts.position.clone(),
                     ctx.accounts.token_a_account.clone(),
@@ -551,7 +733,7 @@ pub mod damm_v2 {
                     ctx.accounts.event_authority.clone(),
                     ctx.accounts.program.clone(),
                 ],
-                &[]
+                ctx.signer_seeds,  // Escrow authority signs as position owner
             )?;
             Ok(())
         }
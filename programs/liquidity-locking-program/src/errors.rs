@@ -22,4 +22,48 @@ pub enum ErrorCode {
     Unauthorized,
     #[msg("Lock is not active")]
     LockNotActive,
+    #[msg("Invalid reward mint")]
+    InvalidRewardMint,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Fee exceeds maximum")]
+    FeeTooHigh,
+    #[msg("Invalid treasury account")]
+    InvalidTreasury,
+    #[msg("No pending admin")]
+    NoPendingAdmin,
+    #[msg("Invalid duration tier")]
+    InvalidTier,
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+    #[msg("Token mint does not match the pool")]
+    InvalidMint,
+    #[msg("Invalid Meteora position")]
+    InvalidPosition,
+    #[msg("Operation not supported for this lock mode")]
+    UnsupportedLockMode,
+    #[msg("Invalid lock owner")]
+    InvalidOwner,
+    #[msg("Invalid lock receipt")]
+    InvalidReceipt,
+    #[msg("Lock has not been fully unlocked")]
+    LockNotClaimed,
+    #[msg("Lock still has unreleased rewards")]
+    RewardsPending,
+    #[msg("Token vault does not match the pool")]
+    InvalidVault,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Emergency mode is not enabled")]
+    EmergencyNotEnabled,
+    #[msg("Pool is not activated yet")]
+    PoolNotActivated,
+    #[msg("Early unlock is not available for this lock")]
+    EarlyUnlockDisabled,
+    #[msg("Lock has expired, use unlock instead")]
+    LockExpired,
+    #[msg("Pool is paused")]
+    PoolPaused,
+    #[msg("Reward multiplier out of range")]
+    InvalidMultiplier,
 }
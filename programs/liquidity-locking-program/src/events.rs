@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, FeeSources, PauseFlags, PoolConfig, TopUpPolicy};
+
+// Emitted when a new lock is created
+#[event]
+pub struct LiquidityLocked {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub tier_index: u8,
+    pub lock_start: u64,
+    pub lock_end: u64,
+    pub receipt_mint: Pubkey,
+}
+
+// Emitted when a lock is fully unlocked and the position NFT is returned
+#[event]
+pub struct LiquidityUnlocked {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when part of a lock's liquidity is withdrawn
+#[event]
+pub struct PartialUnlock {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity: u128,
+    pub liquidity_remaining: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when a lock is exited before expiry (amounts are what the user kept;
+// the penalty goes to the remaining lockers when redistributed, the treasury otherwise)
+#[event]
+pub struct EarlyUnlocked {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub penalty_bps: u16,
+    pub penalty_a: u64,
+    pub penalty_b: u64,
+    pub redistributed: bool,
+    pub timestamp: u64,
+}
+
+// Emitted when liquidity is added to an active lock
+#[event]
+pub struct LockIncreased {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity_added: u128,
+    pub liquidity_locked: u128,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub lock_end: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when an active lock is extended to a later lock_end
+#[event]
+pub struct LockExtended {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub previous_lock_end: u64,
+    pub lock_end: u64,
+    pub tier_index: u8,
+    pub reward_multiplier_bps: u16,
+    pub timestamp: u64,
+}
+
+// Emitted when a lock moves to another wallet
+#[event]
+pub struct LockTransferred {
+    pub lock_account: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub timestamp: u64,
+}
+
+// Emitted when a fully unlocked lock is closed and its rent reclaimed
+#[event]
+pub struct LockClosed {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub position_closed: bool,
+    pub timestamp: u64,
+}
+
+// Emitted when a user pulls their position NFT out of escrow in emergency mode
+#[event]
+pub struct EmergencyWithdrawn {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub liquidity: u128,
+    pub timestamp: u64,
+}
+
+// Emitted when rewards are harvested and/or released to the user
+#[event]
+pub struct RewardsClaimed {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub harvested: u64,
+    pub protocol_fee: u64,
+    pub released: u64,
+    pub total_rewards_earned: u64,
+    pub rewards_claimed: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when trading fees are harvested from an escrowed position
+#[event]
+pub struct FeesClaimed {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub position_nft_mint: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub protocol_fee_a: u64,
+    pub protocol_fee_b: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when redistributed early unlock penalties are paid out to a locker
+#[event]
+pub struct PenaltyRewardsClaimed {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when the admin tops up a pool's incentive budget
+#[event]
+pub struct IncentivesFunded {
+    pub pool_config: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub incentives_remaining: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when a pool's incentive emission rate or end time changes
+#[event]
+pub struct EmissionRateUpdated {
+    pub pool_config: Pubkey,
+    pub pool: Pubkey,
+    pub emission_rate: u64,
+    pub emission_end: u64,
+    pub timestamp: u64,
+}
+
+// Emitted when accrued program incentives are paid out to a locker
+#[event]
+pub struct IncentivesClaimed {
+    pub lock_account: Pubkey,
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub timestamp: u64,
+}
+
+// Emitted whenever the global Config changes
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub fee_bps: u16,
+    pub slf_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_sources: FeeSources,
+    pub guardian: Pubkey,
+    pub paused: PauseFlags,
+    pub emergency: bool,
+    pub timestamp: u64,
+}
+
+// Emitted whenever a PoolConfig changes
+#[event]
+pub struct PoolConfigUpdated {
+    pub pool_config: Pubkey,
+    pub pool: Pubkey,
+    pub fee_bps: u16,
+    pub reward_mint: Pubkey,
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub top_up_policy: TopUpPolicy,
+    pub receipt_transferable: bool,
+    pub paused: PauseFlags,
+    pub min_lock_duration: u64,
+    pub max_lock_duration: u64,
+    pub timestamp: u64,
+}
+
+impl ConfigUpdated {
+    pub fn new(config: &Config, timestamp: u64) -> Self {
+        Self {
+            admin: config.admin,
+            pending_admin: config.pending_admin,
+            fee_bps: config.fee_bps,
+            slf_mint: config.slf_mint,
+            treasury: config.treasury,
+            fee_sources: config.fee_sources,
+            guardian: config.guardian,
+            paused: config.paused,
+            emergency: config.emergency,
+            timestamp,
+        }
+    }
+}
+
+impl PoolConfigUpdated {
+    pub fn new(pool_config_key: Pubkey, pool_config: &PoolConfig, timestamp: u64) -> Self {
+        Self {
+            pool_config: pool_config_key,
+            pool: pool_config.pool,
+            fee_bps: pool_config.fee_bps,
+            reward_mint: pool_config.reward_mint,
+            token_a_mint: pool_config.token_a_mint,
+            token_b_mint: pool_config.token_b_mint,
+            top_up_policy: pool_config.top_up_policy,
+            receipt_transferable: pool_config.receipt_transferable,
+            paused: pool_config.paused,
+            min_lock_duration: pool_config.min_lock_duration,
+            max_lock_duration: pool_config.max_lock_duration,
+            timestamp,
+        }
+    }
+}
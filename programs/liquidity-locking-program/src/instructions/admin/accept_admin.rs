@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::states::Config;
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    // Config PDA
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        constraint = config.pending_admin != Pubkey::default() @ ErrorCode::NoPendingAdmin,
+        constraint = config.pending_admin == new_admin.key() @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Proposed admin (signer)
+    pub new_admin: Signer<'info>,
+}
+
+pub fn handle_accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.new_admin.key();
+    config.pending_admin = Pubkey::default();
+
+    emit_cpi!(ConfigUpdated::new(&ctx.accounts.config, Clock::get()?.unix_timestamp as u64));
+
+    Ok(())
+}
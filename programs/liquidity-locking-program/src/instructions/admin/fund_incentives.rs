@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::states::{Config, PoolConfig};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::IncentivesFunded;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundIncentives<'info> {
+    // Config PDA (admin, incentive mint for the first funding)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (incentive accounting, vault authority)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool_config.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Box<Account<'info, PoolConfig>>,
+
+    // Incentive vault (holds funded incentives until they are claimed)
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [seeds::INCENTIVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump,
+        token::mint = incentive_mint,
+        token::authority = pool_config,
+        token::token_program = token_program,
+    )]
+    pub incentive_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Admin's incentive token account (source of the funding)
+    #[account(
+        mut,
+        token::mint = incentive_mint,
+        token::authority = admin,
+        token::token_program = token_program,
+    )]
+    pub admin_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Incentive mint (SLERF at the first funding, then whatever the pool recorded)
+    #[account(
+        constraint = incentive_mint.key() == pool_config.incentive_mint
+            || (pool_config.incentive_mint == Pubkey::default() && incentive_mint.key() == config.slf_mint)
+            @ ErrorCode::InvalidRewardMint,
+        mint::token_program = token_program,
+    )]
+    pub incentive_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Programs
+    pub token_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub system_program: Program<'info, System>,
+
+    // Admin (signer, payer, must match config)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+// Tops up the pool's incentive budget; emissions stop early once it runs out
+pub fn handle_fund_incentives(ctx: Context<FundIncentives>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return err!(ErrorCode::InsufficientFunds);
+    }
+
+    // Bring emissions up to date so the new budget only pays for time from now on
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.pool_config.update_incentives(now)?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::TransferChecked {
+                from: ctx.accounts.admin_token_account.to_account_info(),
+                mint: ctx.accounts.incentive_mint.to_account_info(),
+                to: ctx.accounts.incentive_vault.to_account_info(),
+                authority: ctx.accounts.admin.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.incentive_mint.decimals,
+    )?;
+
+    // The first funding fixes the pool's incentive mint, so a later slf_mint change cannot strand the vault
+    let pool_config = &mut ctx.accounts.pool_config;
+    pool_config.incentive_mint = ctx.accounts.incentive_mint.key();
+    pool_config.incentives_remaining = pool_config.incentives_remaining
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit_cpi!(IncentivesFunded {
+        pool_config: ctx.accounts.pool_config.key(),
+        pool: ctx.accounts.pool_config.pool,
+        amount,
+        incentives_remaining: ctx.accounts.pool_config.incentives_remaining,
+        timestamp: now,
+    });
+
+    Ok(())
+}
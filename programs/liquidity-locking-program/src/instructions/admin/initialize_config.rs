@@ -1,7 +1,10 @@
 use anchor_lang::prelude::*;
-use crate::states::Config;
-use crate::constants::seeds;
+use crate::states::{Config, FeeSources, PauseFlags};
+use crate::constants::{seeds, MAX_FEE_BPS};
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     // Config PDA (initialize once)
@@ -24,15 +27,28 @@ pub struct InitializeConfig<'info> {
 
 pub fn handle_initialize_config(
     ctx: Context<InitializeConfig>,
-    pool_id: Pubkey,
     fee_bps: u16,
     slf_mint: Pubkey,
+    treasury: Pubkey,
+    fee_sources: FeeSources,
 ) -> Result<()> {
+    // Validate fee cap
+    if fee_bps > MAX_FEE_BPS {
+        return err!(ErrorCode::FeeTooHigh);
+    }
+
     let config = &mut ctx.accounts.config;
-    config.pool_id = pool_id;
     config.admin = ctx.accounts.admin.key();
     config.fee_bps = fee_bps;
     config.slf_mint = slf_mint;
+    config.treasury = treasury;
+    config.fee_sources = fee_sources;
+    config.pending_admin = Pubkey::default();
+    config.guardian = Pubkey::default();
+    config.paused = PauseFlags::default();
+    config.emergency = false;
+
+    emit_cpi!(ConfigUpdated::new(&ctx.accounts.config, Clock::get()?.unix_timestamp as u64));
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, PoolConfig, DurationTier, Pool, TopUpPolicy, PauseFlags};
+use crate::constants::{seeds, BPS_DENOMINATOR, MAX_FEE_BPS, MAX_DURATION_TIERS, MAX_MULTIPLIER_BPS, METEORA_PROGRAM_ID};
+use crate::errors::ErrorCode;
+use crate::events::PoolConfigUpdated;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitializePoolConfig<'info> {
+    // Config PDA (admin and defaults)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (one per Meteora pool)
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + std::mem::size_of::<PoolConfig>(),
+        seeds = [seeds::POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Meteora pool
+    /// CHECK: Pool account (must be owned by Meteora)
+    #[account(owner = METEORA_PROGRAM_ID @ ErrorCode::InvalidPool)]
+    pub pool: UncheckedAccount<'info>,
+
+    // Admin (signer, payer)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // System program for PDA creation
+    pub system_program: Program<'info, System>,
+}
+
+// fee_bps and reward_mint fall back to the global Config defaults
+pub fn handle_initialize_pool_config(
+    ctx: Context<InitializePoolConfig>,
+    tiers: Vec<DurationTier>,
+    min_lock_duration: u64,
+    max_lock_duration: u64,
+    fee_bps: Option<u16>,
+    reward_mint: Option<Pubkey>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let fee_bps = fee_bps.unwrap_or(config.fee_bps);
+
+    // Validate fee cap
+    if fee_bps > MAX_FEE_BPS {
+        return err!(ErrorCode::FeeTooHigh);
+    }
+
+    // Validate raw duration bounds
+    if min_lock_duration > max_lock_duration {
+        return err!(ErrorCode::InvalidDuration);
+    }
+
+    // Read the pool's mints so user paths can validate token accounts against them
+    let pool_state = Pool::load(&ctx.accounts.pool)?;
+
+    // Validate tiers
+    if tiers.len() > MAX_DURATION_TIERS {
+        return err!(ErrorCode::InvalidTier);
+    }
+    let mut tier_slots = [DurationTier::default(); MAX_DURATION_TIERS];
+    for (slot, tier) in tier_slots.iter_mut().zip(tiers) {
+        if tier.enabled && tier.duration_seconds == 0 {
+            return err!(ErrorCode::InvalidTier);
+        }
+        if tier.early_unlock_penalty_bps as u64 > BPS_DENOMINATOR {
+            return err!(ErrorCode::InvalidTier);
+        }
+        if !(BPS_DENOMINATOR as u16..=MAX_MULTIPLIER_BPS).contains(&tier.reward_multiplier_bps) {
+            return err!(ErrorCode::InvalidMultiplier);
+        }
+        *slot = tier;
+    }
+
+    ctx.accounts.pool_config.set_inner(PoolConfig {
+        pool: ctx.accounts.pool.key(),
+        tiers: tier_slots,
+        min_lock_duration,
+        max_lock_duration,
+        fee_bps,
+        reward_mint: reward_mint.unwrap_or(config.slf_mint),
+        token_a_mint: pool_state.token_a_mint,
+        token_b_mint: pool_state.token_b_mint,
+        top_up_policy: TopUpPolicy::default(),
+        receipt_transferable: false,
+        paused: PauseFlags::default(),
+        total_liquidity_locked: 0,
+        penalty_per_liquidity_a: [0; 32],
+        penalty_per_liquidity_b: [0; 32],
+        total_incentive_weight: 0,
+        incentive_per_weight: [0; 32],
+        emission_rate: 0,
+        emission_end: 0,
+        last_emission_time: 0,
+        incentives_remaining: 0,
+        incentive_mint: Pubkey::default(),
+        bump: ctx.bumps.pool_config,
+    });
+
+    emit_cpi!(PoolConfigUpdated::new(
+        ctx.accounts.pool_config.key(),
+        &ctx.accounts.pool_config,
+        Clock::get()?.unix_timestamp as u64,
+    ));
+
+    Ok(())
+}
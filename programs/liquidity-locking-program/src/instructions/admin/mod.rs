@@ -1,2 +1,34 @@
 pub mod initialize_config;  
-pub use initialize_config::*;
\ No newline at end of file
+pub use initialize_config::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod propose_admin;
+pub use propose_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod initialize_pool_config;
+pub use initialize_pool_config::*;
+
+pub mod update_pool_config;
+pub use update_pool_config::*;
+
+pub mod set_duration_tier;
+pub use set_duration_tier::*;
+pub mod pause;
+pub use pause::*;
+
+pub mod unpause;
+pub use unpause::*;
+
+pub mod set_emergency_mode;
+pub use set_emergency_mode::*;
+
+pub mod fund_incentives;
+pub use fund_incentives::*;
+
+pub mod set_emission_rate;
+pub use set_emission_rate::*;
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, PauseFlags, PoolConfig};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::{ConfigUpdated, PoolConfigUpdated};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    // Config PDA (global flags, guardian and admin)
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        constraint = config.can_pause(&authority.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (pauses only this pool when provided)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool_config.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Option<Account<'info, PoolConfig>>,
+
+    // Guardian or admin (signer)
+    pub authority: Signer<'info>,
+}
+
+// Halts every action set in `flags`, others are left as they are
+pub fn handle_pause(ctx: Context<Pause>, flags: PauseFlags) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp as u64;
+
+    if let Some(pool_config) = ctx.accounts.pool_config.as_mut() {
+        pool_config.paused.apply(flags, true);
+        emit_cpi!(PoolConfigUpdated::new(pool_config.key(), pool_config, timestamp));
+    } else {
+        ctx.accounts.config.paused.apply(flags, true);
+        emit_cpi!(ConfigUpdated::new(&ctx.accounts.config, timestamp));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::states::Config;
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    // Config PDA
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Current admin (signer)
+    pub admin: Signer<'info>,
+}
+
+// Passing Pubkey::default() cancels a pending proposal
+pub fn handle_propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = new_admin;
+
+    emit_cpi!(ConfigUpdated::new(&ctx.accounts.config, Clock::get()?.unix_timestamp as u64));
+
+    Ok(())
+}
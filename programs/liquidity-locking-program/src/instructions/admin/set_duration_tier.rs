@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, PoolConfig, DurationTier};
+use crate::constants::{seeds, BPS_DENOMINATOR, MAX_DURATION_TIERS, MAX_MULTIPLIER_BPS};
+use crate::errors::ErrorCode;
+use crate::events::PoolConfigUpdated;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetDurationTier<'info> {
+    // Config PDA (admin)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool_config.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Admin (signer, must match config)
+    pub admin: Signer<'info>,
+}
+
+// Adds, replaces or disables (enabled = false) the tier at `index`
+pub fn handle_set_duration_tier(
+    ctx: Context<SetDurationTier>,
+    index: u8,
+    tier: DurationTier,
+) -> Result<()> {
+    // Validate tier
+    if index as usize >= MAX_DURATION_TIERS {
+        return err!(ErrorCode::InvalidTier);
+    }
+    if tier.enabled && tier.duration_seconds == 0 {
+        return err!(ErrorCode::InvalidTier);
+    }
+    if tier.early_unlock_penalty_bps as u64 > BPS_DENOMINATOR {
+        return err!(ErrorCode::InvalidTier);
+    }
+    if !(BPS_DENOMINATOR as u16..=MAX_MULTIPLIER_BPS).contains(&tier.reward_multiplier_bps) {
+        return err!(ErrorCode::InvalidMultiplier);
+    }
+
+    ctx.accounts.pool_config.tiers[index as usize] = tier;
+
+    emit_cpi!(PoolConfigUpdated::new(
+        ctx.accounts.pool_config.key(),
+        &ctx.accounts.pool_config,
+        Clock::get()?.unix_timestamp as u64,
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::states::Config;
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEmergencyMode<'info> {
+    // Config PDA
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Admin (signer, must match config)
+    pub admin: Signer<'info>,
+}
+
+// Declares Meteora compromised: users may pull their NFTs out of escrow before lock_end
+pub fn handle_set_emergency_mode(ctx: Context<SetEmergencyMode>, enabled: bool) -> Result<()> {
+    ctx.accounts.config.emergency = enabled;
+
+    emit_cpi!(ConfigUpdated::new(&ctx.accounts.config, Clock::get()?.unix_timestamp as u64));
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, PoolConfig};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::EmissionRateUpdated;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetEmissionRate<'info> {
+    // Config PDA (admin)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool_config.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Admin (signer, must match config)
+    pub admin: Signer<'info>,
+}
+
+// emission_rate is in incentive tokens per second; a rate of 0 pauses emissions
+pub fn handle_set_emission_rate(
+    ctx: Context<SetEmissionRate>,
+    emission_rate: u64,
+    emission_end: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    if emission_rate > 0 && emission_end <= now {
+        return err!(ErrorCode::InvalidDuration);
+    }
+
+    // Everything up to now is emitted at the old rate
+    let pool_config = &mut ctx.accounts.pool_config;
+    pool_config.update_incentives(now)?;
+    pool_config.emission_rate = emission_rate;
+    pool_config.emission_end = emission_end;
+
+    emit_cpi!(EmissionRateUpdated {
+        pool_config: ctx.accounts.pool_config.key(),
+        pool: ctx.accounts.pool_config.pool,
+        emission_rate,
+        emission_end,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, FeeSources};
+use crate::constants::{seeds, MAX_FEE_BPS};
+use crate::errors::ErrorCode;
+use crate::events::ConfigUpdated;
+
+// Fields left as None are kept unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigParams {
+    pub fee_bps: Option<u16>,
+    pub slf_mint: Option<Pubkey>,
+    pub treasury: Option<Pubkey>,
+    pub fee_sources: Option<FeeSources>,
+    pub guardian: Option<Pubkey>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    // Config PDA
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // Admin (signer, must match config)
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_update_config(
+    ctx: Context<UpdateConfig>,
+    params: UpdateConfigParams,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(fee_bps) = params.fee_bps {
+        // Validate fee cap
+        if fee_bps > MAX_FEE_BPS {
+            return err!(ErrorCode::FeeTooHigh);
+        }
+        config.fee_bps = fee_bps;
+    }
+    if let Some(slf_mint) = params.slf_mint {
+        config.slf_mint = slf_mint;
+    }
+    if let Some(treasury) = params.treasury {
+        config.treasury = treasury;
+    }
+    if let Some(fee_sources) = params.fee_sources {
+        config.fee_sources = fee_sources;
+    }
+    if let Some(guardian) = params.guardian {
+        config.guardian = guardian;
+    }
+
+    emit_cpi!(ConfigUpdated::new(&ctx.accounts.config, Clock::get()?.unix_timestamp as u64));
+
+    Ok(())
+}
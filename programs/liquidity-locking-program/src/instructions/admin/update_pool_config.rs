@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use crate::states::{Config, PoolConfig, TopUpPolicy};
+use crate::constants::{seeds, MAX_FEE_BPS};
+use crate::errors::ErrorCode;
+use crate::events::PoolConfigUpdated;
+
+// Fields left as None are kept unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdatePoolConfigParams {
+    pub min_lock_duration: Option<u64>,
+    pub max_lock_duration: Option<u64>,
+    pub fee_bps: Option<u16>,
+    pub reward_mint: Option<Pubkey>,
+    pub top_up_policy: Option<TopUpPolicy>,
+    pub receipt_transferable: Option<bool>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdatePoolConfig<'info> {
+    // Config PDA (admin)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool_config.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Admin (signer, must match config)
+    pub admin: Signer<'info>,
+}
+
+pub fn handle_update_pool_config(
+    ctx: Context<UpdatePoolConfig>,
+    params: UpdatePoolConfigParams,
+) -> Result<()> {
+    let pool_config = &mut ctx.accounts.pool_config;
+
+    if let Some(min_lock_duration) = params.min_lock_duration {
+        pool_config.min_lock_duration = min_lock_duration;
+    }
+    if let Some(max_lock_duration) = params.max_lock_duration {
+        pool_config.max_lock_duration = max_lock_duration;
+    }
+    // Validate raw duration bounds
+    if pool_config.min_lock_duration > pool_config.max_lock_duration {
+        return err!(ErrorCode::InvalidDuration);
+    }
+    if let Some(fee_bps) = params.fee_bps {
+        // Validate fee cap
+        if fee_bps > MAX_FEE_BPS {
+            return err!(ErrorCode::FeeTooHigh);
+        }
+        pool_config.fee_bps = fee_bps;
+    }
+    if let Some(reward_mint) = params.reward_mint {
+        // Applies to new locks; existing locks keep claiming the mint recorded at lock time
+        pool_config.reward_mint = reward_mint;
+    }
+    if let Some(top_up_policy) = params.top_up_policy {
+        pool_config.top_up_policy = top_up_policy;
+    }
+    if let Some(receipt_transferable) = params.receipt_transferable {
+        pool_config.receipt_transferable = receipt_transferable;
+    }
+
+    emit_cpi!(PoolConfigUpdated::new(
+        ctx.accounts.pool_config.key(),
+        &ctx.accounts.pool_config,
+        Clock::get()?.unix_timestamp as u64,
+    ));
+
+    Ok(())
+}
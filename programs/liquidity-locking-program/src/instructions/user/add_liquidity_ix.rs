@@ -64,11 +64,16 @@ pub struct DammV2AddLiquidity<'info> {
     pub damm_program: UncheckedAccount<'info>,
 }
 
-pub fn handle_add_liquidity(ctx: Context<DammV2AddLiquidity>, liquidity_delta: u128) -> Result<()> {
+pub fn handle_add_liquidity(
+    ctx: Context<DammV2AddLiquidity>,
+    liquidity_delta: u128,
+    token_a_amount_threshold: u64,
+    token_b_amount_threshold: u64,
+) -> Result<()> {
     let add_params = AddLiquidityParameters {
         liquidity_delta, // Use the passed value
-        token_a_amount_threshold: 10000000,
-        token_b_amount_threshold: 10000000,
+        token_a_amount_threshold, // Max token A in
+        token_b_amount_threshold, // Max token B in
     };
     let add_accounts = AddLiquidity {
         pool: ctx.accounts.pool.to_account_info(),
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::states::{LockAccount, Config, PoolConfig};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::IncentivesClaimed;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimIncentives<'info> {
+    // Config PDA (global pause flags)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (mutable for emissions, signs for the vault)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for the incentive checkpoint; exited locks keep what they earned)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Receipt NFT held by the user (authorizes the holder of locks with a receipt)
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Incentive vault (funded by fund_incentives)
+    #[account(
+        mut,
+        seeds = [seeds::INCENTIVE_VAULT_SEED, pool_config.key().as_ref()],
+        bump,
+    )]
+    pub incentive_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // User's incentive token account (receives incentives)
+    #[account(
+        mut,
+        token::mint = incentive_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_incentive_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Incentive mint (recorded on the pool by its first funding)
+    #[account(
+        address = pool_config.incentive_mint @ ErrorCode::InvalidRewardMint,
+        mint::token_program = token_program,
+    )]
+    pub incentive_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Programs
+    pub token_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+
+    // User (signer)
+    pub user: Signer<'info>,
+}
+
+pub fn handle_claim_incentives(ctx: Context<ClaimIncentives>) -> Result<()> {
+    // Halted while claims are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.claims)?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+    ctx.accounts.lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.pool_config.update_incentives(now)?;
+
+    let lock_account = &mut ctx.accounts.lock_account;
+    lock_account.settle_incentives(&mut ctx.accounts.pool_config, now)?;
+    let amount = lock_account.incentives_owed;
+    lock_account.incentives_owed = 0;
+
+    let pool = ctx.accounts.pool_config.pool;
+    let pool_config_bump = ctx.accounts.pool_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::POOL_CONFIG_SEED, pool.as_ref(), &[pool_config_bump]]];
+
+    if amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.incentive_vault.to_account_info(),
+                    mint: ctx.accounts.incentive_mint.to_account_info(),
+                    to: ctx.accounts.user_incentive_account.to_account_info(),
+                    authority: ctx.accounts.pool_config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.incentive_mint.decimals,
+        )?;
+    }
+
+    emit_cpi!(IncentivesClaimed {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool,
+        amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,235 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::context::damm_v2::{cpi::accounts::ClaimPositionFee, cpi::claim_position_fee};
+use crate::states::{LockAccount, LockStatus, Config, PoolConfig, Pool, Position};
+use crate::constants::{seeds, METEORA_PROGRAM_ID, POOL_AUTHORITY};
+use crate::errors::ErrorCode;
+use crate::events::FeesClaimed;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimLockedFees<'info> {
+    // Config PDA (protocol fee settings)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (pool the lock belongs to)
+    #[account(
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (lock must still be active for the escrow to own the position)
+    #[account(
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        constraint = lock_account.status == LockStatus::Active @ ErrorCode::LockNotActive,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Receipt NFT held by the user (authorizes the holder of locks with a receipt)
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Escrow Authority PDA (owner of the position, signs claim_position_fee)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Escrow ATA for NFT (already exists from lock_liquidity)
+    /// CHECK: Escrow NFT ATA (Token-2022)
+    pub escrow_nft_account: UncheckedAccount<'info>,
+
+    // User's token accounts (for receiving fees)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = user,
+        token::token_program = token_a_program,
+    )]
+    pub user_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = user,
+        token::token_program = token_b_program,
+    )]
+    pub user_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Treasury token accounts (for receiving the protocol fee)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::token_program = token_a_program,
+        constraint = treasury_token_a.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::token_program = token_b_program,
+        constraint = treasury_token_b.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Meteora accounts
+    /// CHECK: Pool authority
+    #[account(address = POOL_AUTHORITY)]
+    pub pool_authority: UncheckedAccount<'info>,
+    /// CHECK: Pool
+    #[account(address = pool_config.pool @ ErrorCode::InvalidPool)]
+    pub pool: UncheckedAccount<'info>,
+    /// CHECK: Position PDA
+    #[account(mut, address = lock_account.position_pda @ ErrorCode::InvalidPosition)]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: Token A vault
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+    /// CHECK: Token B vault
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+    #[account(
+        address = pool_config.token_a_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = pool_config.token_b_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Meteora event authority
+    #[account(
+        seeds = [seeds::EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub damm_event_authority: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    /// CHECK: Meteora program
+    #[account(address = METEORA_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    // User (signer)
+    pub user: Signer<'info>,
+}
+
+pub fn handle_claim_locked_fees(ctx: Context<ClaimLockedFees>) -> Result<()> {
+    // Halted while claims are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.claims)?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+
+    // Meteora accounts must match the pool and the lock's position
+    Pool::load(&ctx.accounts.pool)?.validate_tokens(
+        &ctx.accounts.token_a_vault.key(),
+        &ctx.accounts.token_b_vault.key(),
+        &ctx.accounts.token_a_mint.key(),
+        &ctx.accounts.token_b_mint.key(),
+    )?;
+    Position::load(&ctx.accounts.position)?.validate(&ctx.accounts.pool.key(), &ctx.accounts.position_nft_mint.key())?;
+
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+    let balance_a_before = ctx.accounts.user_token_a.amount;
+    let balance_b_before = ctx.accounts.user_token_b.amount;
+
+    // CPI: Claim position fee straight into the user's token accounts
+    let claim_accounts = ClaimPositionFee {
+        pool_authority: ctx.accounts.pool_authority.to_account_info(),
+        pool: ctx.accounts.pool.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        token_a_account: ctx.accounts.user_token_a.to_account_info(),
+        token_b_account: ctx.accounts.user_token_b.to_account_info(),
+        token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+        token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+        token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
+        token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
+        position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(),
+        owner: ctx.accounts.escrow_authority.to_account_info(),
+        token_a_program: ctx.accounts.token_a_program.to_account_info(),
+        token_b_program: ctx.accounts.token_b_program.to_account_info(),
+        event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+        program: ctx.accounts.damm_program.to_account_info(),
+    };
+    claim_position_fee(CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), claim_accounts, signer_seeds))?;
+
+    ctx.accounts.user_token_a.reload()?;
+    ctx.accounts.user_token_b.reload()?;
+    let token_a_amount = ctx.accounts.user_token_a.amount.saturating_sub(balance_a_before);
+    let token_b_amount = ctx.accounts.user_token_b.amount.saturating_sub(balance_b_before);
+
+    // Skim the protocol fee from what was harvested
+    let (fee_a, fee_b) = if ctx.accounts.config.fee_sources.on_fees {
+        (
+            ctx.accounts.pool_config.protocol_fee(token_a_amount),
+            ctx.accounts.pool_config.protocol_fee(token_b_amount),
+        )
+    } else {
+        (0, 0)
+    };
+
+    if fee_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_a_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_a.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            fee_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if fee_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_b_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_b.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            fee_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+
+    emit_cpi!(FeesClaimed {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        token_a_amount,
+        token_b_amount,
+        protocol_fee_a: fee_a,
+        protocol_fee_b: fee_b,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+
+    Ok(())
+}
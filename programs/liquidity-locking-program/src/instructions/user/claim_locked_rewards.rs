@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::context::damm_v2::{cpi::accounts::ClaimReward, cpi::claim_reward};
+use crate::states::{LockAccount, LockStatus, Config, PoolConfig, Pool, Position};
+use crate::constants::{seeds, METEORA_PROGRAM_ID, POOL_AUTHORITY};
+use crate::errors::ErrorCode;
+use crate::events::RewardsClaimed;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimLockedRewards<'info> {
+    // Config PDA
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (pool the lock belongs to)
+    #[account(
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for reward accounting)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Receipt NFT held by the user (authorizes the holder of locks with a receipt)
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Escrow Authority PDA (owner of the position, signs claim_reward)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Escrow ATA for NFT (already exists from lock_liquidity)
+    /// CHECK: Escrow NFT ATA (Token-2022)
+    pub escrow_nft_account: UncheckedAccount<'info>,
+
+    // Escrow reward account (holds claimed rewards until they vest)
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_reward_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // User's reward account (receives vested rewards)
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub user_reward_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Treasury reward account (for receiving the protocol fee)
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::token_program = token_program,
+        constraint = treasury_reward_account.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_reward_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Reward mint, fixed per lock so a pool-level change cannot strand escrowed rewards.
+    // Vesting is tracked for this one mint: Meteora reward slots paying another mint keep
+    // accruing on the position and are claimed from Meteora once the NFT is released.
+    #[account(
+        address = lock_account.reward_mint @ ErrorCode::InvalidRewardMint,
+        mint::token_program = token_program,
+    )]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Meteora accounts
+    /// CHECK: Pool authority
+    #[account(address = POOL_AUTHORITY)]
+    pub pool_authority: UncheckedAccount<'info>,
+    /// CHECK: Pool
+    #[account(mut, address = pool_config.pool @ ErrorCode::InvalidPool)]
+    pub pool: UncheckedAccount<'info>,
+    /// CHECK: Position PDA
+    #[account(mut, address = lock_account.position_pda @ ErrorCode::InvalidPosition)]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: Meteora reward vault for the claimed reward index
+    #[account(mut)]
+    pub reward_vault: UncheckedAccount<'info>,
+    /// CHECK: Meteora event authority
+    #[account(
+        seeds = [seeds::EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub damm_event_authority: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022 reward mint
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Meteora program
+    #[account(address = METEORA_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    // User (signer, payer)
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Clock for vesting
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handle_claim_locked_rewards(
+    ctx: Context<ClaimLockedRewards>,
+    reward_index: u8,
+) -> Result<()> {
+    // Halted while claims are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.claims)?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+    ctx.accounts.lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+
+    let now = ctx.accounts.clock.unix_timestamp as u64;
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+
+    let mut harvested = 0;
+    let mut protocol_fee = 0;
+
+    // Harvest from Meteora only while the escrow still holds the position
+    if ctx.accounts.lock_account.status == LockStatus::Active {
+        // Meteora accounts must match the pool's reward slot and the lock's position
+        Pool::load(&ctx.accounts.pool)?.validate_reward(
+            reward_index,
+            &ctx.accounts.reward_vault.key(),
+            &ctx.accounts.reward_mint.key(),
+        )?;
+        Position::load(&ctx.accounts.position)?.validate(&ctx.accounts.pool.key(), &ctx.accounts.position_nft_mint.key())?;
+
+        let balance_before = ctx.accounts.escrow_reward_account.amount;
+
+        // CPI: Claim reward into escrow reward account
+        let claim_accounts = ClaimReward {
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            reward_vault: ctx.accounts.reward_vault.to_account_info(),
+            reward_mint: ctx.accounts.reward_mint.to_account_info(),
+            user_token_account: ctx.accounts.escrow_reward_account.to_account_info(),
+            position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(),
+            owner: ctx.accounts.escrow_authority.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+            program: ctx.accounts.damm_program.to_account_info(),
+        };
+        claim_reward(
+            CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), claim_accounts, signer_seeds),
+            reward_index,
+            0, // Do not skip reward
+        )?;
+
+        ctx.accounts.escrow_reward_account.reload()?;
+        harvested = ctx.accounts.escrow_reward_account.amount
+            .checked_sub(balance_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Skim the protocol fee before the rewards start vesting
+        if ctx.accounts.config.fee_sources.on_rewards {
+            protocol_fee = ctx.accounts.pool_config.protocol_fee(harvested);
+            if protocol_fee > 0 {
+                token_interface::transfer_checked(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        TransferChecked {
+                            from: ctx.accounts.escrow_reward_account.to_account_info(),
+                            mint: ctx.accounts.reward_mint.to_account_info(),
+                            to: ctx.accounts.treasury_reward_account.to_account_info(),
+                            authority: ctx.accounts.escrow_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    protocol_fee,
+                    ctx.accounts.reward_mint.decimals,
+                )?;
+            }
+        }
+
+        let lock_account = &mut ctx.accounts.lock_account;
+        lock_account.total_rewards_earned = lock_account.total_rewards_earned
+            .checked_add(harvested - protocol_fee)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    // Release whatever has vested since the last claim
+    let vested = ctx.accounts.lock_account.vested_rewards(now);
+    let claimable = vested.saturating_sub(ctx.accounts.lock_account.rewards_claimed);
+
+    if claimable > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_reward_account.to_account_info(),
+                    mint: ctx.accounts.reward_mint.to_account_info(),
+                    to: ctx.accounts.user_reward_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            claimable,
+            ctx.accounts.reward_mint.decimals,
+        )?;
+    }
+
+    // Update Lock Account
+    let lock_account = &mut ctx.accounts.lock_account;
+    lock_account.rewards_claimed = lock_account.rewards_claimed
+        .checked_add(claimable)
+        .ok_or(ErrorCode::MathOverflow)?;
+    lock_account.last_claim_time = now;
+
+    emit_cpi!(RewardsClaimed {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        reward_mint: ctx.accounts.reward_mint.key(),
+        harvested,
+        protocol_fee,
+        released: claimable,
+        total_rewards_earned: ctx.accounts.lock_account.total_rewards_earned,
+        rewards_claimed: ctx.accounts.lock_account.rewards_claimed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
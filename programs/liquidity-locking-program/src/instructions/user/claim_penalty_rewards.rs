@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
+use crate::states::{LockAccount, Config, PoolConfig};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::PenaltyRewardsClaimed;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimPenaltyRewards<'info> {
+    // Config PDA (global pause flags)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (penalty accumulators, signs for the vaults)
+    #[account(
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for the penalty checkpoint; exited locks keep what they earned)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Receipt NFT held by the user (authorizes the holder of locks with a receipt)
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Penalty vaults (filled by early unlocks in this pool)
+    #[account(
+        mut,
+        seeds = [seeds::PENALTY_VAULT_SEED, pool_config.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+    )]
+    pub penalty_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [seeds::PENALTY_VAULT_SEED, pool_config.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+    )]
+    pub penalty_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // User's token accounts (for receiving penalties)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = user,
+        token::token_program = token_a_program,
+    )]
+    pub user_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = user,
+        token::token_program = token_b_program,
+    )]
+    pub user_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Pool token mints
+    #[account(
+        address = pool_config.token_a_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = pool_config.token_b_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    // Programs
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+
+    // User (signer)
+    pub user: Signer<'info>,
+}
+
+pub fn handle_claim_penalty_rewards(ctx: Context<ClaimPenaltyRewards>) -> Result<()> {
+    // Halted while claims are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.claims)?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+    ctx.accounts.lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+
+    let lock_account = &mut ctx.accounts.lock_account;
+    lock_account.settle_penalties(&ctx.accounts.pool_config)?;
+    let token_a_amount = lock_account.penalty_owed_a;
+    let token_b_amount = lock_account.penalty_owed_b;
+    lock_account.penalty_owed_a = 0;
+    lock_account.penalty_owed_b = 0;
+
+    let pool = ctx.accounts.pool_config.pool;
+    let pool_config_bump = ctx.accounts.pool_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::POOL_CONFIG_SEED, pool.as_ref(), &[pool_config_bump]]];
+
+    if token_a_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_a_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.penalty_vault_a.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.user_token_a.to_account_info(),
+                    authority: ctx.accounts.pool_config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_a_amount,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if token_b_amount > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_b_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.penalty_vault_b.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.user_token_b.to_account_info(),
+                    authority: ctx.accounts.pool_config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_b_amount,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+
+    emit_cpi!(PenaltyRewardsClaimed {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool,
+        token_a_amount,
+        token_b_amount,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Token;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, TokenAccount, TokenInterface};
+use crate::context::damm_v2::{cpi::accounts::ClosePosition, cpi::close_position};
+use crate::states::{LockAccount, LockStatus};
+use crate::constants::{seeds, METEORA_PROGRAM_ID, POOL_AUTHORITY};
+use crate::errors::ErrorCode;
+use crate::events::LockClosed;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseLock<'info> {
+    // Lock Account PDA (closed, rent goes back to the user)
+    #[account(
+        mut,
+        close = user,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        constraint = lock_account.is_owned_by(&user.key(), None) @ ErrorCode::Unauthorized,  // The receipt is burned by now
+        constraint = lock_account.status == LockStatus::Claimed @ ErrorCode::LockNotClaimed,
+        constraint = lock_account.rewards_claimed >= lock_account.total_rewards_earned @ ErrorCode::RewardsPending,
+        constraint = lock_account.penalty_owed_a == 0 && lock_account.penalty_owed_b == 0 @ ErrorCode::RewardsPending,
+        constraint = lock_account.incentives_owed == 0 @ ErrorCode::RewardsPending,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds, closed by close_position)
+    /// CHECK: Position NFT mint
+    #[account(mut, address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Escrow Authority PDA (owns the escrow token accounts, receipt close authority)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Escrow token accounts left over from the lock (closed when given, must be empty)
+    #[account(
+        mut,
+        token::authority = escrow_authority,
+        token::token_program = token_program,
+    )]
+    pub escrow_reward_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::authority = escrow_authority,
+        token::token_program = token_a_program,
+    )]
+    pub escrow_token_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::authority = escrow_authority,
+        token::token_program = token_b_program,
+    )]
+    pub escrow_token_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Receipt NFT mint (locks with a receipt only, closable once its NFT is burned)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    // User's NFT ATA (holds the NFT since the full unlock)
+    /// CHECK: User NFT ATA (Token-2022), validated by Meteora
+    #[account(mut)]
+    pub user_nft_account: UncheckedAccount<'info>,
+
+    // Meteora accounts (only used when closing the position)
+    /// CHECK: Pool authority
+    #[account(address = POOL_AUTHORITY)]
+    pub pool_authority: UncheckedAccount<'info>,
+    /// CHECK: Pool
+    #[account(mut, address = lock_account.pool @ ErrorCode::InvalidPool)]
+    pub pool: UncheckedAccount<'info>,
+    /// CHECK: Position PDA
+    #[account(mut, address = lock_account.position_pda @ ErrorCode::InvalidPosition)]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: Meteora event authority
+    #[account(
+        seeds = [seeds::EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub damm_event_authority: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_2022_program: Program<'info, Token2022>,  // For Token-2022 NFT and receipt
+    pub token_program: Program<'info, Token>,  // For SPL reward token (SLERF)
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    /// CHECK: Meteora program
+    #[account(address = METEORA_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    // User (signer, rent receiver)
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+// close_position also closes the empty Meteora position (and burns its NFT) for its rent
+pub fn handle_close_lock(ctx: Context<CloseLock>, close_position_account: bool) -> Result<()> {
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+
+    // CPI: Close the escrow's empty token accounts, rent goes back to the user
+    let escrow_accounts = [
+        (&ctx.accounts.escrow_reward_account, ctx.accounts.token_program.to_account_info()),
+        (&ctx.accounts.escrow_token_a, ctx.accounts.token_a_program.to_account_info()),
+        (&ctx.accounts.escrow_token_b, ctx.accounts.token_b_program.to_account_info()),
+    ];
+    for (escrow_account, token_program) in escrow_accounts {
+        if let Some(escrow_account) = escrow_account {
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_program,
+                token_interface::CloseAccount {
+                    account: escrow_account.to_account_info(),
+                    destination: ctx.accounts.user.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+    }
+
+    // CPI: Close the receipt mint, its NFT was burned when the lock was exited
+    if let Some(receipt_mint) = &ctx.accounts.receipt_mint {
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: receipt_mint.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    if close_position_account {
+        // CPI: Close the empty position, the user owns its NFT since the full unlock
+        let close_accounts = ClosePosition {
+            position_nft_mint: ctx.accounts.position_nft_mint.to_account_info(),
+            position_nft_account: ctx.accounts.user_nft_account.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            rent_receiver: ctx.accounts.user.to_account_info(),
+            owner: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+            program: ctx.accounts.damm_program.to_account_info(),
+        };
+        close_position(CpiContext::new(ctx.accounts.damm_program.to_account_info(), close_accounts))?;
+    }
+
+    emit_cpi!(LockClosed {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        position_closed: close_position_account,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+
+    Ok(())
+}
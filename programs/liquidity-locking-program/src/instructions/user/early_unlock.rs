@@ -0,0 +1,426 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::context::damm_v2::{cpi::accounts::RemoveAllLiquidity, cpi::remove_all_liquidity};
+use crate::states::{LockAccount, LockStatus, LockMode, Config, PoolConfig, Pool, Position};
+use crate::constants::{seeds, BPS_DENOMINATOR, METEORA_PROGRAM_ID, POOL_AUTHORITY};
+use crate::errors::ErrorCode;
+use crate::events::EarlyUnlocked;
+use crate::receipt::ReceiptAccounts;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EarlyUnlock<'info> {
+    // Config PDA (protocol fee settings, treasury)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (pool the lock belongs to, penalty accumulators and vault authority)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for updates, ownership checked in handler)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        constraint = lock_account.status == LockStatus::Active @ ErrorCode::LockNotActive,
+        constraint = lock_account.mode == LockMode::Escrow @ ErrorCode::UnsupportedLockMode,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for validation and ATA mint reference)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Escrow Authority PDA (owner of the position, signs remove liquidity and the NFT transfer)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // User's token accounts (for receiving removed tokens)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = user,
+        token::token_program = token_a_program,
+    )]
+    pub user_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = user,
+        token::token_program = token_b_program,
+    )]
+    pub user_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Treasury token accounts (for receiving the penalty and protocol fee)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::token_program = token_a_program,
+        constraint = treasury_token_a.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::token_program = token_b_program,
+        constraint = treasury_token_b.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Penalty vaults (hold penalties redistributed to the remaining lockers)
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [seeds::PENALTY_VAULT_SEED, pool_config.key().as_ref(), token_a_mint.key().as_ref()],
+        bump,
+        token::mint = token_a_mint,
+        token::authority = pool_config,
+        token::token_program = token_a_program,
+    )]
+    pub penalty_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        seeds = [seeds::PENALTY_VAULT_SEED, pool_config.key().as_ref(), token_b_mint.key().as_ref()],
+        bump,
+        token::mint = token_b_mint,
+        token::authority = pool_config,
+        token::token_program = token_b_program,
+    )]
+    pub penalty_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Escrow ATA for NFT (closed once emptied)
+    /// CHECK: Escrow NFT ATA (Token-2022)
+    #[account(mut)]
+    pub escrow_nft_account: UncheckedAccount<'info>,
+
+    // User's NFT ATA (receives the NFT, created if needed in handler)
+    /// CHECK: User NFT ATA (Token-2022)
+    #[account(mut)]
+    pub user_nft_account: UncheckedAccount<'info>,
+
+    // Receipt NFT (locks with a receipt only, burned with the lock)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Meteora accounts
+    /// CHECK: Pool authority
+    #[account(address = POOL_AUTHORITY)]
+    pub pool_authority: UncheckedAccount<'info>,
+    /// CHECK: Pool
+    #[account(mut, address = pool_config.pool @ ErrorCode::InvalidPool)]
+    pub pool: UncheckedAccount<'info>,
+    /// CHECK: Position PDA
+    #[account(mut, address = lock_account.position_pda @ ErrorCode::InvalidPosition)]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: Token A vault
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+    /// CHECK: Token B vault
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+    #[account(
+        address = pool_config.token_a_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = pool_config.token_b_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Meteora event authority
+    #[account(
+        seeds = [seeds::EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub damm_event_authority: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_2022_program: Program<'info, Token2022>,  // For Token-2022 NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Meteora program
+    #[account(address = METEORA_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    // User (signer)
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    // Clock for expiry and penalty scaling
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// Exits an escrow lock before lock_end. The penalty shrinks linearly to zero over the lock,
+// is taken from the withdrawn tokens on top of the protocol fee and is shared by the pool's remaining lockers.
+pub fn handle_early_unlock(
+    ctx: Context<EarlyUnlock>,
+    token_a_min_out: u64,
+    token_b_min_out: u64,
+) -> Result<()> {
+    // Halted while unlocks are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.unlocks)?;
+
+    // Meteora accounts must match the pool and the lock's position
+    Pool::load(&ctx.accounts.pool)?.validate_tokens(
+        &ctx.accounts.token_a_vault.key(),
+        &ctx.accounts.token_b_vault.key(),
+        &ctx.accounts.token_a_mint.key(),
+        &ctx.accounts.token_b_mint.key(),
+    )?;
+    Position::load(&ctx.accounts.position)?.validate(&ctx.accounts.pool.key(), &ctx.accounts.position_nft_mint.key())?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+
+    let now = ctx.accounts.clock.unix_timestamp as u64;
+    if now >= ctx.accounts.lock_account.lock_end {
+        return err!(ErrorCode::LockExpired);
+    }
+    if ctx.accounts.lock_account.early_unlock_penalty_bps == 0 {
+        return err!(ErrorCode::EarlyUnlockDisabled);
+    }
+    let penalty_bps = ctx.accounts.lock_account.early_unlock_penalty_at(now);
+
+    let liquidity = ctx.accounts.lock_account.liquidity_locked;
+    let balance_a_before = ctx.accounts.user_token_a.amount;
+    let balance_b_before = ctx.accounts.user_token_b.amount;
+
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+
+    // CPI: Withdraw everything to the user, the penalty is taken from it below
+    let remove_accounts = RemoveAllLiquidity {
+        pool_authority: ctx.accounts.pool_authority.to_account_info(),
+        pool: ctx.accounts.pool.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        token_a_account: ctx.accounts.user_token_a.to_account_info(),
+        token_b_account: ctx.accounts.user_token_b.to_account_info(),
+        token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+        token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+        token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
+        token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
+        position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(), // Escrow keeps custody
+        owner: ctx.accounts.escrow_authority.to_account_info(),
+        token_a_program: ctx.accounts.token_a_program.to_account_info(),
+        token_b_program: ctx.accounts.token_b_program.to_account_info(),
+        event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+        program: ctx.accounts.damm_program.to_account_info(),
+    };
+    remove_all_liquidity(
+        CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), remove_accounts, signer_seeds),
+        token_a_min_out,
+        token_b_min_out,
+    )?;
+
+    // Create user's NFT ATA if it doesn't exist
+    anchor_spl::associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        anchor_spl::associated_token::Create {
+            payer: ctx.accounts.user.to_account_info(),
+            associated_token: ctx.accounts.user_nft_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+            mint: ctx.accounts.position_nft_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+        },
+    ))?;
+
+    // Transfer NFT from escrow to user (Token-2022)
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_nft_account.to_account_info(),
+                to: ctx.accounts.user_nft_account.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+                mint: ctx.accounts.position_nft_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,   // NFT amount
+        0,   // Decimals for NFT
+    )?;
+
+    // CPI: Close the now-empty escrow NFT ATA, rent goes back to the user
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_2022_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: ctx.accounts.escrow_nft_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.escrow_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    // The receipt is spent together with the lock
+    if ctx.accounts.lock_account.has_receipt() {
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let receipt = ReceiptAccounts {
+            receipt_mint: receipt_mint.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            payer: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        receipt.revoke(user_receipt_account.to_account_info(), signer_seeds)?;
+    }
+
+    ctx.accounts.user_token_a.reload()?;
+    ctx.accounts.user_token_b.reload()?;
+    let withdrawn_a = ctx.accounts.user_token_a.amount.saturating_sub(balance_a_before);
+    let withdrawn_b = ctx.accounts.user_token_b.amount.saturating_sub(balance_b_before);
+
+    // Penalty and protocol fee are both taken from the gross withdrawn amounts
+    let penalty_a = (withdrawn_a as u128 * penalty_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    let penalty_b = (withdrawn_b as u128 * penalty_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    let (fee_a, fee_b) = if ctx.accounts.config.fee_sources.on_unlock {
+        (
+            ctx.accounts.pool_config.protocol_fee(withdrawn_a).min(withdrawn_a - penalty_a),
+            ctx.accounts.pool_config.protocol_fee(withdrawn_b).min(withdrawn_b - penalty_b),
+        )
+    } else {
+        (0, 0)
+    };
+    let token_a_amount = withdrawn_a - penalty_a - fee_a;
+    let token_b_amount = withdrawn_b - penalty_b - fee_b;
+
+    // Validate slippage on what the user actually keeps
+    if token_a_amount < token_a_min_out || token_b_amount < token_b_min_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+
+    // The exiting lock settles first and stops counting, so its own penalty goes to the others
+    ctx.accounts.pool_config.update_incentives(now)?;
+    ctx.accounts.lock_account.settle(&mut ctx.accounts.pool_config, now)?;
+    ctx.accounts.pool_config.remove_lock(&ctx.accounts.lock_account)?;
+
+    // With no locker left to receive it, the penalty goes to the treasury
+    let redistributed = ctx.accounts.pool_config.total_liquidity_locked > 0;
+    let (to_vault_a, to_vault_b) = if redistributed { (penalty_a, penalty_b) } else { (0, 0) };
+    let to_treasury_a = fee_a + penalty_a - to_vault_a;
+    let to_treasury_b = fee_b + penalty_b - to_vault_b;
+
+    if to_treasury_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_a_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_a.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            to_treasury_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if to_treasury_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_b_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_b.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            to_treasury_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+    let vault_a_before = ctx.accounts.penalty_vault_a.amount;
+    let vault_b_before = ctx.accounts.penalty_vault_b.amount;
+    if to_vault_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_a_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.penalty_vault_a.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            to_vault_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if to_vault_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_b_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.penalty_vault_b.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            to_vault_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+
+    // Credit lockers with what reached the vaults (Token-2022 transfer fees are withheld on the way)
+    if redistributed {
+        ctx.accounts.penalty_vault_a.reload()?;
+        ctx.accounts.penalty_vault_b.reload()?;
+        let received_a = ctx.accounts.penalty_vault_a.amount.saturating_sub(vault_a_before);
+        let received_b = ctx.accounts.penalty_vault_b.amount.saturating_sub(vault_b_before);
+        ctx.accounts.pool_config.distribute_penalties(received_a, received_b)?;
+    }
+
+    // Update Lock Account
+    let lock_account = &mut ctx.accounts.lock_account;
+    lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+    lock_account.liquidity_locked = 0;
+    lock_account.status = LockStatus::Claimed;
+
+    emit_cpi!(EarlyUnlocked {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        liquidity,
+        token_a_amount,
+        token_b_amount,
+        penalty_bps,
+        penalty_a,
+        penalty_b,
+        redistributed,
+        timestamp: now,
+    });
+
+    Ok(())
+}
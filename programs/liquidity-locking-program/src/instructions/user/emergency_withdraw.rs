@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, TokenAccount, TransferChecked};
+use crate::states::{LockAccount, LockMode, LockStatus, Config, PoolConfig};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::EmergencyWithdrawn;
+use crate::receipt::ReceiptAccounts;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    // Config PDA (emergency mode must be enabled; pauses do not apply)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        constraint = config.emergency @ ErrorCode::EmergencyNotEnabled,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (pool the lock belongs to, tracks its locked liquidity)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for status, ownership checked in handler)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        constraint = lock_account.status == LockStatus::Active @ ErrorCode::LockNotActive,
+        constraint = lock_account.mode != LockMode::Permanent @ ErrorCode::UnsupportedLockMode,  // Permanent locks never release the NFT
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for validation and ATA mint reference)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Escrow Authority PDA (holds the NFT, signs the transfer)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Escrow ATA for NFT (closed once emptied)
+    /// CHECK: Escrow NFT ATA (Token-2022)
+    #[account(mut)]
+    pub escrow_nft_account: UncheckedAccount<'info>,
+
+    // User's NFT ATA (receives the NFT, created if needed in handler)
+    /// CHECK: User NFT ATA (Token-2022)
+    #[account(mut)]
+    pub user_nft_account: UncheckedAccount<'info>,
+
+    // Receipt NFT (locks with a receipt only, burned with the lock)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Programs
+    pub token_2022_program: Program<'info, Token2022>,  // For Token-2022 NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // User (signer, payer)
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+// Hands the position NFT back without touching Meteora; the liquidity stays in the position
+pub fn handle_emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+
+    // Create user's NFT ATA if it doesn't exist
+    anchor_spl::associated_token::create_idempotent(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        anchor_spl::associated_token::Create {
+            payer: ctx.accounts.user.to_account_info(),
+            associated_token: ctx.accounts.user_nft_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+            mint: ctx.accounts.position_nft_mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+        },
+    ))?;
+
+    // Transfer NFT from escrow to user (Token-2022)
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_nft_account.to_account_info(),
+                to: ctx.accounts.user_nft_account.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+                mint: ctx.accounts.position_nft_mint.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,   // NFT amount
+        0,   // Decimals for NFT
+    )?;
+
+    // CPI: Close the now-empty escrow NFT ATA, rent goes back to the user
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_2022_program.to_account_info(),
+        token_interface::CloseAccount {
+            account: ctx.accounts.escrow_nft_account.to_account_info(),
+            destination: ctx.accounts.user.to_account_info(),
+            authority: ctx.accounts.escrow_authority.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    // The receipt is spent together with the lock
+    if ctx.accounts.lock_account.has_receipt() {
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let receipt = ReceiptAccounts {
+            receipt_mint: receipt_mint.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            payer: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        receipt.revoke(user_receipt_account.to_account_info(), signer_seeds)?;
+    }
+
+    // Update Lock Account (the liquidity now belongs to the NFT holder)
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.pool_config.update_incentives(now)?;
+    let lock_account = &mut ctx.accounts.lock_account;
+    let liquidity = lock_account.liquidity_locked;
+    lock_account.settle(&mut ctx.accounts.pool_config, now)?;
+    ctx.accounts.pool_config.remove_lock(lock_account)?;
+    lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+    lock_account.liquidity_locked = 0;
+    lock_account.status = LockStatus::Claimed;
+
+    emit_cpi!(EmergencyWithdrawn {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        liquidity,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount;
+use crate::states::{LockAccount, LockStatus, LockMode, LockDuration, PoolConfig, Config};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::LockExtended;
+use crate::receipt::{ReceiptAccounts, receipt_fields};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExtendLock<'info> {
+    // Config PDA (global pause flags)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (validates the new duration, tracks incentive weight)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for lock_end and tier)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        constraint = lock_account.status == LockStatus::Active @ ErrorCode::LockNotActive,
+        constraint = lock_account.mode == LockMode::Escrow @ ErrorCode::UnsupportedLockMode,  // Meteora vesting schedules are fixed
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Receipt NFT held by the user (authorizes the holder of locks with a receipt)
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Receipt NFT mint (locks with a receipt only, its metadata mirrors liquidity and lock_end)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    // Escrow Authority PDA (receipt metadata update authority)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Programs
+    pub token_2022_program: Program<'info, Token2022>,  // For the Token-2022 receipt
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // User (signer, lock owner, pays for receipt metadata growth)
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn handle_extend_lock(ctx: Context<ExtendLock>, duration: LockDuration) -> Result<()> {
+    // Halted while top-ups are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.top_ups)?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+    ctx.accounts.lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+
+    // Validate duration against the pool's tiers and bounds
+    let resolved = ctx.accounts.pool_config.resolve_duration(duration)?;
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    let lock_end = now.checked_add(resolved.duration_seconds).ok_or(ErrorCode::MathOverflow)?;
+
+    ctx.accounts.pool_config.update_incentives(now)?;
+    let lock_account = &mut ctx.accounts.lock_account;
+    let previous_lock_end = lock_account.lock_end;
+
+    // lock_end may only move later
+    if lock_end <= previous_lock_end {
+        return err!(ErrorCode::InvalidDuration);
+    }
+    // The reward multiplier may only go up
+    if resolved.reward_multiplier_bps < lock_account.reward_multiplier_bps {
+        return err!(ErrorCode::InvalidTier);
+    }
+
+    // Update Lock Account (new tier applies from now on, incentives so far accrue at the old multiplier)
+    lock_account.settle(&mut ctx.accounts.pool_config, now)?;
+    ctx.accounts.pool_config.remove_lock(lock_account)?;
+    lock_account.restart_window(now, lock_end);  // The new tier is priced from now on
+    lock_account.duration_seconds = resolved.duration_seconds;
+    lock_account.tier_index = resolved.tier_index;
+    lock_account.reward_multiplier_bps = resolved.reward_multiplier_bps;
+    lock_account.early_unlock_penalty_bps = resolved.early_unlock_penalty_bps;
+    ctx.accounts.pool_config.add_lock(lock_account)?;
+
+    // Keep the receipt metadata in line with the lock
+    if ctx.accounts.lock_account.has_receipt() {
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let lock_account_key = ctx.accounts.lock_account.key();
+        let escrow_bump = ctx.bumps.escrow_authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+        let receipt = ReceiptAccounts {
+            receipt_mint: receipt_mint.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            payer: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        receipt.update_fields(
+            receipt_fields(&ctx.accounts.lock_account.pool, ctx.accounts.lock_account.liquidity_locked, lock_end),
+            signer_seeds,
+        )?;
+    }
+
+    emit_cpi!(LockExtended {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.lock_account.pool,
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        previous_lock_end,
+        lock_end,
+        tier_index: resolved.tier_index,
+        reward_multiplier_bps: resolved.reward_multiplier_bps,
+        timestamp: now,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,359 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::context::damm_v2::{cpi::accounts::AddLiquidity, cpi::add_liquidity, AddLiquidityParameters};
+use crate::states::{LockAccount, LockStatus, LockMode, PoolConfig, Pool, Position, Config};
+use crate::constants::{seeds, METEORA_PROGRAM_ID};
+use crate::errors::ErrorCode;
+use crate::events::LockIncreased;
+use crate::receipt::{ReceiptAccounts, receipt_fields};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct IncreaseLock<'info> {
+    // Config PDA (global pause flags)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    // PoolConfig PDA (pool the lock belongs to, holds the top-up policy and locked liquidity)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Box<Account<'info, PoolConfig>>,
+
+    // Lock Account PDA (mutable for liquidity and lock_end)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        constraint = lock_account.status == LockStatus::Active @ ErrorCode::LockNotActive,
+        constraint = lock_account.mode == LockMode::Escrow @ ErrorCode::UnsupportedLockMode,  // Meteora vesting schedules are fixed
+    )]
+    pub lock_account: Box<Account<'info, LockAccount>>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Receipt NFT held by the user (authorizes the holder of locks with a receipt)
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Receipt NFT mint (locks with a receipt only, its metadata mirrors liquidity and lock_end)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+
+    // Escrow Authority PDA (owner of the position, signs add_liquidity)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Escrow ATA for NFT (already exists from the lock)
+    /// CHECK: Escrow NFT ATA (Token-2022)
+    pub escrow_nft_account: UncheckedAccount<'info>,
+
+    // User's token accounts (source of the top-up)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = user,
+        token::token_program = token_a_program,
+    )]
+    pub user_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = user,
+        token::token_program = token_b_program,
+    )]
+    pub user_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Escrow token accounts (stage the top-up, since the escrow authority owns the position)
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_a_mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_a_program,
+    )]
+    pub escrow_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_b_mint,
+        associated_token::authority = escrow_authority,
+        associated_token::token_program = token_b_program,
+    )]
+    pub escrow_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Meteora accounts
+    /// CHECK: Pool
+    #[account(mut, address = pool_config.pool @ ErrorCode::InvalidPool)]
+    pub pool: UncheckedAccount<'info>,
+    /// CHECK: Position PDA
+    #[account(mut, address = lock_account.position_pda @ ErrorCode::InvalidPosition)]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: Token A vault
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+    /// CHECK: Token B vault
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+    #[account(
+        address = pool_config.token_a_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = pool_config.token_b_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Meteora event authority
+    #[account(
+        seeds = [seeds::EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub damm_event_authority: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_2022_program: Program<'info, Token2022>,  // For the Token-2022 receipt
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Meteora program
+    #[account(address = METEORA_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    // User (signer, payer)
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn handle_increase_lock(
+    ctx: Context<IncreaseLock>,
+    liquidity_delta: u128,
+    token_a_max_in: u64,
+    token_b_max_in: u64,
+) -> Result<()> {
+    // Halted while top-ups are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.top_ups)?;
+
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+    ctx.accounts.lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+
+    // Pool must be live, and Meteora accounts must match it and the lock's position
+    let pool_state = Pool::load(&ctx.accounts.pool)?;
+    pool_state.ensure_active(&Clock::get()?)?;
+    pool_state.validate_tokens(
+        &ctx.accounts.token_a_vault.key(),
+        &ctx.accounts.token_b_vault.key(),
+        &ctx.accounts.token_a_mint.key(),
+        &ctx.accounts.token_b_mint.key(),
+    )?;
+    Position::load(&ctx.accounts.position)?.validate(&ctx.accounts.pool.key(), &ctx.accounts.position_nft_mint.key())?;
+
+    if liquidity_delta == 0 {
+        return err!(ErrorCode::InvalidLiquidity);
+    }
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+    let escrow_a_before = ctx.accounts.escrow_token_a.amount;
+    let escrow_b_before = ctx.accounts.escrow_token_b.amount;
+
+    // Stage only what add_liquidity will pull from escrow, grossed up for the Token-2022
+    // transfer fee of both hops (user -> escrow, escrow -> vault)
+    let (amount_a, amount_b) = pool_state.liquidity_amounts(liquidity_delta)?;
+    let deposit_a = transfer_fee_included_amount(
+        &ctx.accounts.token_a_mint,
+        transfer_fee_included_amount(&ctx.accounts.token_a_mint, amount_a)?,
+    )?;
+    let deposit_b = transfer_fee_included_amount(
+        &ctx.accounts.token_b_mint,
+        transfer_fee_included_amount(&ctx.accounts.token_b_mint, amount_b)?,
+    )?;
+
+    // Validate slippage before anything moves
+    if deposit_a > token_a_max_in || deposit_b > token_b_max_in {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_a_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_a.to_account_info(),
+                mint: ctx.accounts.token_a_mint.to_account_info(),
+                to: ctx.accounts.escrow_token_a.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        deposit_a,
+        ctx.accounts.token_a_mint.decimals,
+    )?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_b_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_b.to_account_info(),
+                mint: ctx.accounts.token_b_mint.to_account_info(),
+                to: ctx.accounts.escrow_token_b.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        deposit_b,
+        ctx.accounts.token_b_mint.decimals,
+    )?;
+
+    // CPI: Add liquidity to the escrowed position
+    let add_params = AddLiquidityParameters {
+        liquidity_delta,
+        token_a_amount_threshold: token_a_max_in,
+        token_b_amount_threshold: token_b_max_in,
+    };
+    let add_accounts = AddLiquidity {
+        pool: ctx.accounts.pool.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        token_a_account: ctx.accounts.escrow_token_a.to_account_info(),
+        token_b_account: ctx.accounts.escrow_token_b.to_account_info(),
+        token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+        token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+        token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
+        token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
+        position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(),
+        owner: ctx.accounts.escrow_authority.to_account_info(),
+        token_a_program: ctx.accounts.token_a_program.to_account_info(),
+        token_b_program: ctx.accounts.token_b_program.to_account_info(),
+        event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+        program: ctx.accounts.damm_program.to_account_info(),
+    };
+    add_liquidity(
+        CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), add_accounts, signer_seeds),
+        add_params,
+    )?;
+
+    // Refund the rounding dust of the fee gross-up, if any was staged but not deposited
+    ctx.accounts.escrow_token_a.reload()?;
+    ctx.accounts.escrow_token_b.reload()?;
+    let refund_a = ctx.accounts.escrow_token_a.amount.saturating_sub(escrow_a_before);
+    let refund_b = ctx.accounts.escrow_token_b.amount.saturating_sub(escrow_b_before);
+    let token_a_amount = deposit_a.saturating_sub(refund_a);
+    let token_b_amount = deposit_b.saturating_sub(refund_b);
+    if refund_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_a_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_a.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.user_token_a.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if refund_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_b_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_b.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.user_token_b.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            refund_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
+    }
+
+    // Update Lock Account (lock_end moves per the pool's top-up policy)
+    let lock_end = ctx.accounts.pool_config.top_up_lock_end(&ctx.accounts.lock_account, liquidity_delta, now)?;
+    ctx.accounts.pool_config.update_incentives(now)?;
+    let lock_account = &mut ctx.accounts.lock_account;
+    lock_account.settle(&mut ctx.accounts.pool_config, now)?;  // At the old weight
+    ctx.accounts.pool_config.remove_lock(lock_account)?;
+    lock_account.liquidity_locked = lock_account.liquidity_locked
+        .checked_add(liquidity_delta)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if lock_end != lock_account.lock_end {
+        lock_account.restart_window(now, lock_end);
+    }
+    ctx.accounts.pool_config.add_lock(lock_account)?;
+
+    // Keep the receipt metadata in line with the lock
+    if ctx.accounts.lock_account.has_receipt() {
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let receipt = ReceiptAccounts {
+            receipt_mint: receipt_mint.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            payer: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        receipt.update_fields(
+            receipt_fields(&ctx.accounts.pool.key(), ctx.accounts.lock_account.liquidity_locked, lock_end),
+            signer_seeds,
+        )?;
+    }
+
+    emit_cpi!(LockIncreased {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        liquidity_added: liquidity_delta,
+        liquidity_locked: ctx.accounts.lock_account.liquidity_locked,
+        token_a_amount,
+        token_b_amount,
+        lock_end,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// Amount to send so that `amount` arrives net of the mint's Token-2022 transfer fee (as DAMM v2 grosses it up)
+fn transfer_fee_included_amount(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let info = mint.to_account_info();
+    let data = info.try_borrow_data()?;
+    let Ok(mint_state) = StateWithExtensions::<MintState>::unpack(&data) else {
+        return Ok(amount);  // SPL Token mint, no extensions
+    };
+    let Ok(fee_config) = mint_state.get_extension::<TransferFeeConfig>() else {
+        return Ok(amount);
+    };
+    fee_config
+        .get_epoch_fee(Clock::get()?.epoch)
+        .calculate_pre_fee_amount(amount)
+        .ok_or(error!(ErrorCode::MathOverflow))
+}
@@ -0,0 +1,251 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::TokenAccount;
+use crate::context::damm_v2::{cpi::accounts::PermanentLockPosition, cpi::permanent_lock_position};
+use crate::states::{LockAccount, LockStatus, LockMode, PoolConfig, Pool, Position, Config};
+use crate::constants::{seeds, METEORA_PROGRAM_ID, CUSTOM_DURATION_TIER};
+use crate::errors::ErrorCode;
+use crate::events::LiquidityLocked;
+use crate::receipt::{ReceiptAccounts, receipt_fields};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LockForever<'info> {
+    // Config PDA (global pause flags)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (validates the pool)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Escrow Authority PDA (owner of the escrowed NFT, signs permanent_lock_position)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Lock Account PDA (new)
+    #[account(
+        init,
+        payer = user,
+        space = 8 + std::mem::size_of::<LockAccount>(),
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (already minted by Meteora)
+    /// CHECK: Position NFT mint (validated against the position account)
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // User's NFT account (holds the position NFT)
+    #[account(
+        mut,
+        token::mint = position_nft_mint,
+        token::authority = user,
+        token::token_program = token_program,
+    )]
+    pub position_nft_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Escrow ATA for NFT (program-owned, created in handler if missing)
+    /// CHECK: Escrow NFT ATA (Token-2022)
+    #[account(mut)]
+    pub escrow_nft_account: UncheckedAccount<'info>,
+
+    // Receipt NFT mint (Token-2022, created in handler)
+    /// CHECK: Receipt mint PDA, initialized by the receipt helper
+    #[account(
+        mut,
+        seeds = [seeds::RECEIPT_MINT_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    // User's receipt ATA (Token-2022, created in handler)
+    /// CHECK: User receipt ATA, derived and created by the associated token program
+    #[account(mut)]
+    pub user_receipt_account: UncheckedAccount<'info>,
+
+    // Meteora accounts
+    /// CHECK: Pool account (validated by the pool_config seeds)
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+    /// CHECK: Position PDA (deserialized in handler)
+    #[account(
+        mut,
+        seeds = [seeds::POSITION_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub position: UncheckedAccount<'info>,
+    /// CHECK: Meteora event authority
+    #[account(
+        seeds = [seeds::EVENT_AUTHORITY_SEED],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
+    pub damm_event_authority: UncheckedAccount<'info>,
+
+    // Programs
+    pub token_program: Program<'info, token_2022::Token2022>,  // For Token-2022 NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: Meteora program
+    #[account(address = METEORA_PROGRAM_ID)]
+    pub damm_program: UncheckedAccount<'info>,
+
+    // User (signer, payer)
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn handle_lock_forever(ctx: Context<LockForever>) -> Result<()> {
+    // Halted while new locks are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.locks)?;
+
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp as u64;
+
+    // Pool must be enabled and activated
+    Pool::load(&ctx.accounts.pool)?.ensure_active(&clock)?;
+
+    // Read the Meteora position to find how much liquidity it holds
+    let position = Position::load(&ctx.accounts.position)?;
+    position.validate(&ctx.accounts.pool.key(), &ctx.accounts.position_nft_mint.key())?;
+    let liquidity = position.unlocked_liquidity;
+    if liquidity == 0 {
+        return err!(ErrorCode::InvalidLiquidity);
+    }
+
+    // ATA creation for escrow (may already exist from an earlier lock of this NFT)
+    anchor_spl::associated_token::create_idempotent(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            anchor_spl::associated_token::Create {
+                payer: ctx.accounts.user.to_account_info(),
+                associated_token: ctx.accounts.escrow_nft_account.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+                mint: ctx.accounts.position_nft_mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ),
+    )?;
+
+    // Transfer NFT (Token2022) so fee and reward claims keep going through the program
+    token_2022::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_2022::TransferChecked {
+                from: ctx.accounts.position_nft_account.to_account_info(),
+                mint: ctx.accounts.position_nft_mint.to_account_info(),
+                to: ctx.accounts.escrow_nft_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        1,   // NFT amount
+        0,   // Decimals
+    )?;
+
+    // CPI: Permanently lock all unlocked liquidity
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let escrow_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+    let lock_accounts = PermanentLockPosition {
+        pool: ctx.accounts.pool.to_account_info(),
+        position: ctx.accounts.position.to_account_info(),
+        position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(),
+        owner: ctx.accounts.escrow_authority.to_account_info(),
+        event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+        program: ctx.accounts.damm_program.to_account_info(),
+    };
+    permanent_lock_position(
+        CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), lock_accounts, escrow_seeds),
+        liquidity,
+    )?;
+
+    // Mint the receipt NFT that represents the lock
+    let receipt_bump = ctx.bumps.receipt_mint;
+    let mint_seeds: &[&[&[u8]]] = &[&[seeds::RECEIPT_MINT_SEED, lock_account_key.as_ref(), &[receipt_bump]]];
+
+    let receipt = ReceiptAccounts {
+        receipt_mint: ctx.accounts.receipt_mint.to_account_info(),
+        escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+        payer: ctx.accounts.user.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    receipt.create_mint(
+        mint_seeds,
+        escrow_seeds,
+        ctx.accounts.pool_config.receipt_transferable,
+        receipt_fields(&ctx.accounts.pool.key(), liquidity, u64::MAX),
+    )?;
+    receipt.issue(
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.user_receipt_account.to_account_info(),
+        escrow_seeds,
+    )?;
+
+    // Create Lock Account (never expires, earns the pool's best multiplier)
+    // The new lock shares in penalties redistributed and incentives emitted from now on
+    ctx.accounts.pool_config.update_incentives(now)?;
+    let pool_config = &ctx.accounts.pool_config;
+    ctx.accounts.lock_account.set_inner(LockAccount {
+        user: ctx.accounts.user.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        position_pda: ctx.accounts.position.key(),
+        pool: ctx.accounts.pool.key(),
+        lock_start: now,
+        lock_end: u64::MAX,
+        liquidity_locked: liquidity,
+        duration_seconds: 0,
+        tier_index: CUSTOM_DURATION_TIER,
+        reward_multiplier_bps: ctx.accounts.pool_config.max_reward_multiplier_bps(),
+        early_unlock_penalty_bps: 0,  // Permanent locks never unlock
+        status: LockStatus::Active,
+        mode: LockMode::Permanent,
+        vesting: Pubkey::default(),
+        receipt_mint: ctx.accounts.receipt_mint.key(),
+        reward_mint: pool_config.reward_mint,
+        total_rewards_earned: 0,
+        rewards_claimed: 0,
+        rewards_vested_base: 0,
+        last_claim_time: now,
+        penalty_checkpoint_a: pool_config.penalty_per_liquidity_a,
+        penalty_checkpoint_b: pool_config.penalty_per_liquidity_b,
+        penalty_owed_a: 0,
+        penalty_owed_b: 0,
+        incentive_checkpoint: pool_config.incentive_per_weight,
+        incentives_owed: 0,
+    });
+    ctx.accounts.pool_config.add_lock(&ctx.accounts.lock_account)?;
+
+    emit_cpi!(LiquidityLocked {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        position: ctx.accounts.position.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        liquidity,
+        token_a_amount: 0,  // No deposit, the position already holds the liquidity
+        token_b_amount: 0,
+        tier_index: CUSTOM_DURATION_TIER,
+        lock_start: now,
+        lock_end: u64::MAX,
+        receipt_mint: ctx.accounts.receipt_mint.key(),
+    });
+
+    Ok(())
+}
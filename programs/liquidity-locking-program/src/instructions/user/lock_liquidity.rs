@@ -1,24 +1,36 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self};
 use anchor_spl::token_2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::context::damm_v2::{cpi::accounts::CreatePosition, cpi::create_position, cpi::accounts::AddLiquidity, cpi::add_liquidity, AddLiquidityParameters};
-use crate::states::{LockAccount, LockStatus, Config};
-use crate::constants::{seeds, SLERF_USDC_POOL, METEORA_PROGRAM_ID, POOL_AUTHORITY};
-use crate::errors::ErrorCode; 
+use crate::context::damm_v2::{cpi::accounts::LockPosition, cpi::lock_position, VestingParameters};
+use crate::states::{LockAccount, LockStatus, LockMode, LockDuration, PoolConfig, Pool, Config};
+use crate::constants::{seeds, METEORA_PROGRAM_ID, POOL_AUTHORITY, VESTING_PERIOD_SECONDS};
+use crate::errors::ErrorCode;
+use crate::events::LiquidityLocked;
+use crate::receipt::{ReceiptAccounts, receipt_fields};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct LockLiquidity<'info> {
-    // Config PDA
+    // Config PDA (global pause flags)
     #[account(
         seeds = [seeds::CONFIG_SEED],
         bump,
     )]
     pub config: Account<'info, Config>,
 
+    // PoolConfig PDA (validates the pool and its durations)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, pool.key().as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
     // Escrow Authority PDA (signer for ATA)
     #[account(
-        seeds = [seeds::ESCROW_AUTHORITY_SEED],
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
         bump,
     )]
     pub escrow_authority: SystemAccount<'info>,
@@ -28,16 +40,26 @@ pub struct LockLiquidity<'info> {
         init,
         payer = user,
         space = 8 + std::mem::size_of::<LockAccount>(),
-        seeds = [seeds::LOCK_SEED, user.key().as_ref(), position_nft_mint.key().as_ref()],
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
         bump,
     )]
     pub lock_account: Account<'info, LockAccount>,
 
     // User's token accounts (for add_liquidity)
-    #[account(mut)]
-    pub user_token_a: Account<'info, token::TokenAccount>, // SLERF
-    #[account(mut)]
-    pub user_token_b: Account<'info, token::TokenAccount>, // USDC
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = user,
+        token::token_program = token_a_program,
+    )]
+    pub user_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = user,
+        token::token_program = token_b_program,
+    )]
+    pub user_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Position NFT mint (new, signer for create_position)
     #[account(mut)]
@@ -55,12 +77,40 @@ pub struct LockLiquidity<'info> {
     #[account(mut)]
     pub escrow_nft_account: UncheckedAccount<'info>,
 
+    // Meteora vesting account (NativeVesting only, created by lock_position)
+    /// CHECK: Vesting PDA, initialized by Meteora
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_VESTING_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub vesting: Option<UncheckedAccount<'info>>,
+
+    // Receipt NFT mint (Token-2022, created in handler)
+    /// CHECK: Receipt mint PDA, initialized by the receipt helper
+    #[account(
+        mut,
+        seeds = [seeds::RECEIPT_MINT_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    // User's receipt ATA (Token-2022, created in handler)
+    /// CHECK: User receipt ATA, derived and created by the associated token program
+    #[account(mut)]
+    pub user_receipt_account: UncheckedAccount<'info>,
+
     // Meteora accounts
-    /// CHECK: Pool account
+    /// CHECK: Pool account (validated by the pool_config seeds, deserialized in handler)
     #[account(mut)]
     pub pool: UncheckedAccount<'info>,
-    /// CHECK: Position account
-    #[account(mut)]
+    /// CHECK: Position PDA (created by create_position CPI)
+    #[account(
+        mut,
+        seeds = [seeds::POSITION_SEED, position_nft_mint.key().as_ref()],
+        bump,
+        seeds::program = METEORA_PROGRAM_ID,
+    )]
     pub position: UncheckedAccount<'info>,
     /// CHECK: Pool authority
     #[account(address = POOL_AUTHORITY)]
@@ -71,17 +121,23 @@ pub struct LockLiquidity<'info> {
     /// CHECK: Token B vault
     #[account(mut)]
     pub token_b_vault: UncheckedAccount<'info>,
-    /// CHECK: Token A mint (SLERF)
-    pub token_a_mint: UncheckedAccount<'info>,
-    /// CHECK: Token B mint (USDC)
-    pub token_b_mint: UncheckedAccount<'info>,
-    /// CHECK: Event authority
+    #[account(
+        address = pool_config.token_a_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = pool_config.token_b_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Meteora event authority
     #[account(
         seeds = [seeds::EVENT_AUTHORITY_SEED],
         bump,
         seeds::program = METEORA_PROGRAM_ID,
     )]
-    pub event_authority: UncheckedAccount<'info>,
+    pub damm_event_authority: UncheckedAccount<'info>,
 
     // Programs
     pub token_program: Program<'info, token_2022::Token2022>,  // CHANGE: Use Token2022
@@ -91,10 +147,8 @@ pub struct LockLiquidity<'info> {
     #[account(address = METEORA_PROGRAM_ID)]
     pub damm_program: UncheckedAccount<'info>,
 
-    /// CHECK: Token A program (SPL Token)
-    pub token_a_program: UncheckedAccount<'info>,
-    /// CHECK: Token B program (SPL Token)
-    pub token_b_program: UncheckedAccount<'info>,
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
 
     // User (signer, payer)
     #[account(mut)]
@@ -107,29 +161,32 @@ pub struct LockLiquidity<'info> {
 pub fn handle_lock_liquidity(
     ctx: Context<LockLiquidity>,
     liquidity_delta: u128,
-    duration_months: u8,
+    duration: LockDuration,
+    token_a_max_in: u64,
+    token_b_max_in: u64,
+    mode: LockMode,
 ) -> Result<()> {
-    // Validate config pool_id
-    if ctx.accounts.config.pool_id != SLERF_USDC_POOL {
-        return err!(ErrorCode::InvalidPool);
-    }
+    // Halted while new locks are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.locks)?;
 
-    // Validate duration
-    let duration_seconds = match duration_months {
-        3 => 3 * 30 * 24 * 3600, // ~3 months
-        6 => 6 * 30 * 24 * 3600, // ~6 months
-        12 => 12 * 30 * 24 * 3600, // ~12 months
-        _ => return err!(ErrorCode::InvalidDuration),
-    };
+    // Validate duration against the pool's tiers and bounds
+    let resolved = ctx.accounts.pool_config.resolve_duration(duration)?;
+    let pool_state = Pool::load(&ctx.accounts.pool)?;
+    pool_state.ensure_active(&ctx.accounts.clock)?;
+    pool_state.validate_tokens(
+        &ctx.accounts.token_a_vault.key(),
+        &ctx.accounts.token_b_vault.key(),
+        &ctx.accounts.token_a_mint.key(),
+        &ctx.accounts.token_b_mint.key(),
+    )?;
 
     let now = ctx.accounts.clock.unix_timestamp as u64;
-    let lock_end = now + duration_seconds;
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let lock_end = now.checked_add(resolved.duration_seconds).ok_or(ErrorCode::MathOverflow)?;
 
-    // Derive position PDA (for reference in LockAccount)
-    let position_pda = Pubkey::find_program_address(
-        &[seeds::POSITION_SEED, ctx.accounts.position_nft_mint.key().as_ref()],
-        &METEORA_PROGRAM_ID,
-    ).0;
+    // Position PDA (seeds checked by the accounts constraint, stored in LockAccount)
+    let position_pda = ctx.accounts.position.key();
 
     // CPI: Create position
     let create_accounts = CreatePosition {
@@ -142,7 +199,7 @@ pub fn handle_lock_liquidity(
         payer: ctx.accounts.user.to_account_info(),
         token_program: ctx.accounts.token_program.to_account_info(),
         system_program: ctx.accounts.system_program.to_account_info(),
-        event_authority: ctx.accounts.event_authority.to_account_info(),
+        event_authority: ctx.accounts.damm_event_authority.to_account_info(),
         program: ctx.accounts.damm_program.to_account_info(),
     };
     create_position(CpiContext::new(ctx.accounts.damm_program.to_account_info(), create_accounts))?;
@@ -163,10 +220,12 @@ pub fn handle_lock_liquidity(
     )?;
 
     // CPI: Add liquidity
+    let balance_a_before = ctx.accounts.user_token_a.amount;
+    let balance_b_before = ctx.accounts.user_token_b.amount;
     let add_params = AddLiquidityParameters {
         liquidity_delta,
-        token_a_amount_threshold: u64::MAX, // Allow any amount
-        token_b_amount_threshold: u64::MAX,
+        token_a_amount_threshold: token_a_max_in,
+        token_b_amount_threshold: token_b_max_in,
     };
     let add_accounts = AddLiquidity {
         pool: ctx.accounts.pool.to_account_info(),
@@ -181,11 +240,21 @@ pub fn handle_lock_liquidity(
         owner: ctx.accounts.user.to_account_info(),
         token_a_program: ctx.accounts.token_a_program.to_account_info(),
         token_b_program: ctx.accounts.token_b_program.to_account_info(),
-        event_authority: ctx.accounts.event_authority.to_account_info(),
+        event_authority: ctx.accounts.damm_event_authority.to_account_info(),
         program: ctx.accounts.damm_program.to_account_info(),
     };
     add_liquidity(CpiContext::new(ctx.accounts.damm_program.to_account_info(), add_accounts), add_params)?;
 
+    ctx.accounts.user_token_a.reload()?;
+    ctx.accounts.user_token_b.reload()?;
+    let token_a_amount = balance_a_before.saturating_sub(ctx.accounts.user_token_a.amount);
+    let token_b_amount = balance_b_before.saturating_sub(ctx.accounts.user_token_b.amount);
+
+    // Validate slippage
+    if token_a_amount > token_a_max_in || token_b_amount > token_b_max_in {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+
     // Transfer NFT (Token2022)
 token_2022::transfer_checked(
     CpiContext::new(
@@ -201,20 +270,139 @@ token_2022::transfer_checked(
     0,   // Decimals
 )?;
 
+    // Hand the liquidity to a Meteora vesting schedule owned by the escrow
+    let vesting = match mode {
+        LockMode::Escrow => Pubkey::default(),
+        LockMode::NativeVesting => {
+            let vesting = ctx.accounts.vesting.as_ref().ok_or(ErrorCode::InvalidVesting)?;
+            let params = vesting_parameters(&pool_state, &ctx.accounts.clock, resolved.duration_seconds, liquidity_delta)?;
+
+            let vesting_bump = ctx.bumps.vesting.ok_or(ErrorCode::InvalidVesting)?;
+            let signer_seeds: &[&[&[u8]]] = &[
+                &[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]],
+                &[seeds::LOCK_VESTING_SEED, lock_account_key.as_ref(), &[vesting_bump]],
+            ];
+
+            let lock_accounts = LockPosition {
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                vesting: vesting.to_account_info(),
+                position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(),
+                owner: ctx.accounts.escrow_authority.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                event_authority: ctx.accounts.damm_event_authority.to_account_info(),
+                program: ctx.accounts.damm_program.to_account_info(),
+            };
+            lock_position(
+                CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), lock_accounts, signer_seeds),
+                params,
+            )?;
+            vesting.key()
+        }
+        LockMode::Permanent => return err!(ErrorCode::UnsupportedLockMode),  // Use lock_forever
+    };
+
+    // Mint the receipt NFT that represents the lock
+    let receipt_bump = ctx.bumps.receipt_mint;
+    let escrow_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+    let mint_seeds: &[&[&[u8]]] = &[&[seeds::RECEIPT_MINT_SEED, lock_account_key.as_ref(), &[receipt_bump]]];
+
+    let receipt = ReceiptAccounts {
+        receipt_mint: ctx.accounts.receipt_mint.to_account_info(),
+        escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+        payer: ctx.accounts.user.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+    };
+    receipt.create_mint(
+        mint_seeds,
+        escrow_seeds,
+        ctx.accounts.pool_config.receipt_transferable,
+        receipt_fields(&ctx.accounts.pool.key(), liquidity_delta, lock_end),
+    )?;
+    receipt.issue(
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.user_receipt_account.to_account_info(),
+        escrow_seeds,
+    )?;
+
     // Create Lock Account
+    // The new lock shares in penalties redistributed and incentives emitted from now on
+    ctx.accounts.pool_config.update_incentives(now)?;
+    let pool_config = &ctx.accounts.pool_config;
     ctx.accounts.lock_account.set_inner(LockAccount {
         user: ctx.accounts.user.key(),
         position_nft_mint: ctx.accounts.position_nft_mint.key(),
         position_pda,
+        pool: ctx.accounts.pool.key(),
         lock_start: now,
         lock_end,
         liquidity_locked: liquidity_delta,
-        duration_months,
+        duration_seconds: resolved.duration_seconds,
+        tier_index: resolved.tier_index,
+        reward_multiplier_bps: resolved.reward_multiplier_bps,
+        early_unlock_penalty_bps: resolved.early_unlock_penalty_bps,
         status: LockStatus::Active,
+        mode,
+        vesting,
+        receipt_mint: ctx.accounts.receipt_mint.key(),
+        reward_mint: pool_config.reward_mint,
         total_rewards_earned: 0,
         rewards_claimed: 0,
+        rewards_vested_base: 0,
         last_claim_time: now,
+        penalty_checkpoint_a: pool_config.penalty_per_liquidity_a,
+        penalty_checkpoint_b: pool_config.penalty_per_liquidity_b,
+        penalty_owed_a: 0,
+        penalty_owed_b: 0,
+        incentive_checkpoint: pool_config.incentive_per_weight,
+        incentives_owed: 0,
+    });
+    ctx.accounts.pool_config.add_lock(&ctx.accounts.lock_account)?;
+
+    emit_cpi!(LiquidityLocked {
+        lock_account: ctx.accounts.lock_account.key(),
+        user: ctx.accounts.user.key(),
+        pool: ctx.accounts.pool.key(),
+        position: position_pda,
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        liquidity: liquidity_delta,
+        token_a_amount,
+        token_b_amount,
+        tier_index: resolved.tier_index,
+        lock_start: now,
+        lock_end,
+        receipt_mint: ctx.accounts.receipt_mint.key(),
     });
 
     Ok(())
-}
\ No newline at end of file
+}
+
+// Linear daily schedule ending at now + duration; the leftover time and liquidity form the cliff
+fn vesting_parameters(pool: &Pool, clock: &Clock, duration_seconds: u64, liquidity: u128) -> Result<VestingParameters> {
+    let period_frequency = pool.seconds_to_points(VESTING_PERIOD_SECONDS);
+    let number_of_period = (duration_seconds / VESTING_PERIOD_SECONDS).min(u16::MAX as u64);
+    if number_of_period == 0 {
+        return err!(ErrorCode::InvalidDuration);
+    }
+
+    let cliff_seconds = duration_seconds - number_of_period * VESTING_PERIOD_SECONDS;
+    let cliff_point = pool.current_point(clock)
+        .checked_add(pool.seconds_to_points(cliff_seconds))
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let liquidity_per_period = liquidity / number_of_period as u128;
+    if liquidity_per_period == 0 {
+        return err!(ErrorCode::InvalidLiquidity);
+    }
+
+    Ok(VestingParameters {
+        cliff_point: Some(cliff_point),
+        period_frequency,
+        cliff_unlock_liquidity: liquidity - liquidity_per_period * number_of_period as u128,
+        liquidity_per_period,
+        number_of_period: number_of_period as u16,
+    })
+}
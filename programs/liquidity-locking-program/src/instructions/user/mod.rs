@@ -10,5 +10,41 @@ pub use lock_position_ix::*;
 pub mod lock_liquidity;
 pub use lock_liquidity::*;
 
+pub mod lock_existing_position;
+pub use lock_existing_position::*;
+
+pub mod lock_forever;
+pub use lock_forever::*;
+
+pub mod increase_lock;
+pub use increase_lock::*;
+
+pub mod extend_lock;
+pub use extend_lock::*;
+
+pub mod transfer_lock;
+pub use transfer_lock::*;
+
 pub mod unlock_liquidity;
-pub use unlock_liquidity::*;
\ No newline at end of file
+pub use unlock_liquidity::*;
+
+pub mod early_unlock;
+pub use early_unlock::*;
+
+pub mod close_lock;
+pub use close_lock::*;
+
+pub mod emergency_withdraw;
+pub use emergency_withdraw::*;
+
+pub mod claim_locked_rewards;
+pub use claim_locked_rewards::*;
+
+pub mod claim_locked_fees;
+pub use claim_locked_fees::*;
+
+pub mod claim_penalty_rewards;
+pub use claim_penalty_rewards::*;
+
+pub mod claim_incentives;
+pub use claim_incentives::*;
\ No newline at end of file
@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::TokenAccount;
+use crate::states::{LockAccount, LockStatus};
+use crate::constants::seeds;
+use crate::errors::ErrorCode;
+use crate::events::LockTransferred;
+use crate::receipt::ReceiptAccounts;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct TransferLock<'info> {
+    // Lock Account PDA (ownership moves, the address stays the same; checked in handler)
+    #[account(
+        mut,
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
+        bump,
+    )]
+    pub lock_account: Account<'info, LockAccount>,
+
+    // Position NFT mint (for lock account seeds)
+    /// CHECK: Position NFT mint
+    #[account(address = lock_account.position_nft_mint)]
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    // Escrow Authority PDA (receipt mint authority and permanent delegate)
+    #[account(
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
+        bump,
+    )]
+    pub escrow_authority: SystemAccount<'info>,
+
+    // Receipt NFT (locks with a receipt only, re-issued to the new owner)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// CHECK: New owner wallet (authority of the new receipt ATA)
+    #[account(address = new_owner @ ErrorCode::InvalidOwner)]
+    pub new_owner_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: New owner's receipt ATA, created by the associated token program
+    #[account(mut)]
+    pub new_owner_receipt_account: Option<UncheckedAccount<'info>>,
+
+    // Programs
+    pub token_2022_program: Program<'info, Token2022>,  // For the Token-2022 receipt
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    // User (signer, payer, current lock owner)
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+// new_owner can be any wallet or multisig; it does not need to sign
+pub fn handle_transfer_lock(ctx: Context<TransferLock>, new_owner: Pubkey) -> Result<()> {
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
+    }
+    if new_owner == Pubkey::default() || new_owner == ctx.accounts.user.key() {
+        return err!(ErrorCode::InvalidOwner);
+    }
+
+    // Move the receipt: burn it from the current holder and mint a fresh one to the new owner
+    if ctx.accounts.lock_account.has_receipt() && ctx.accounts.lock_account.status == LockStatus::Active {
+        let lock_account_key = ctx.accounts.lock_account.key();
+        let escrow_bump = ctx.bumps.escrow_authority;
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
+
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let new_owner_account = ctx.accounts.new_owner_account.as_ref().ok_or(ErrorCode::InvalidOwner)?;
+        let new_owner_receipt_account = ctx.accounts.new_owner_receipt_account.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+
+        let receipt = ReceiptAccounts {
+            receipt_mint: receipt_mint.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            payer: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        receipt.revoke(user_receipt_account.to_account_info(), signer_seeds)?;
+        receipt.issue(
+            new_owner_account.to_account_info(),
+            new_owner_receipt_account.to_account_info(),
+            signer_seeds,
+        )?;
+    }
+
+    let previous_owner = ctx.accounts.user.key();
+    ctx.accounts.lock_account.user = new_owner;
+
+    emit_cpi!(LockTransferred {
+        lock_account: ctx.accounts.lock_account.key(),
+        position_nft_mint: ctx.accounts.position_nft_mint.key(),
+        previous_owner,
+        new_owner,
+        timestamp: Clock::get()?.unix_timestamp as u64,
+    });
+
+    Ok(())
+}
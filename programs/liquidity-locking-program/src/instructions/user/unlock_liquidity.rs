@@ -1,21 +1,38 @@
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self};
 use anchor_spl::token_2022::{Token2022};
-use anchor_spl::token_interface::TransferChecked;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::context::damm_v2::{cpi::accounts::RemoveLiquidity, cpi::remove_liquidity, cpi::accounts::RemoveAllLiquidity, cpi::remove_all_liquidity, RemoveLiquidityParameters};
-use crate::states::{LockAccount, LockStatus};
-use crate::constants::{seeds, METEORA_PROGRAM_ID};
+use crate::context::damm_v2::{cpi::accounts::RefreshVesting, cpi::refresh_vesting};
+use crate::states::{LockAccount, LockStatus, LockMode, Config, PoolConfig, Pool, Position};
+use crate::constants::{seeds, METEORA_PROGRAM_ID, POOL_AUTHORITY};
 use crate::errors::ErrorCode;
+use crate::events::{LiquidityUnlocked, PartialUnlock};
+use crate::receipt::{ReceiptAccounts, receipt_fields};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UnlockLiquidity<'info> {
-    // Lock Account PDA (mutable for updates)
+    // Config PDA (protocol fee settings)
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    // PoolConfig PDA (pool the lock belongs to, tracks its locked liquidity)
+    #[account(
+        mut,
+        seeds = [seeds::POOL_CONFIG_SEED, lock_account.pool.as_ref()],
+        bump = pool_config.bump,
+    )]
+    pub pool_config: Account<'info, PoolConfig>,
+
+    // Lock Account PDA (mutable for updates, ownership checked in handler)
     #[account(
         mut,
-        seeds = [seeds::LOCK_SEED, user.key().as_ref(), position_nft_mint.key().as_ref()],
+        seeds = [seeds::LOCK_SEED, position_nft_mint.key().as_ref()],
         bump,
-        constraint = lock_account.user == user.key().clone() @ ErrorCode::Unauthorized,
         constraint = lock_account.status == LockStatus::Active @ ErrorCode::LockNotActive,
     )]
     pub lock_account: Account<'info, LockAccount>,
@@ -25,35 +42,71 @@ pub struct UnlockLiquidity<'info> {
     #[account(address = lock_account.position_nft_mint)]
     pub position_nft_mint: UncheckedAccount<'info>,
 
-    // Escrow Authority PDA (for NFT ATA)
+    // Escrow Authority PDA (owner of the position, signs remove liquidity)
     #[account(
-        seeds = [seeds::ESCROW_AUTHORITY_SEED],
+        seeds = [seeds::ESCROW_AUTHORITY_SEED, lock_account.key().as_ref()],
         bump,
     )]
     pub escrow_authority: SystemAccount<'info>,
 
     // User's token accounts (for receiving removed tokens)
-    #[account(mut)]
-    pub user_token_a: Account<'info, token::TokenAccount>,
-    #[account(mut)]
-    pub user_token_b: Account<'info, token::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::authority = user,
+        token::token_program = token_a_program,
+    )]
+    pub user_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::authority = user,
+        token::token_program = token_b_program,
+    )]
+    pub user_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Treasury token accounts (for receiving the protocol fee)
+    #[account(
+        mut,
+        token::mint = token_a_mint,
+        token::token_program = token_a_program,
+        constraint = treasury_token_a.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        token::mint = token_b_mint,
+        token::token_program = token_b_program,
+        constraint = treasury_token_b.owner == config.treasury @ ErrorCode::InvalidTreasury,
+    )]
+    pub treasury_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
     // Escrow ATA for NFT (already exists from lock_liquidity)
     /// CHECK: Escrow NFT ATA (Token-2022)
     #[account(mut)]
     pub escrow_nft_account: UncheckedAccount<'info>,
 
-    // User's NFT ATA (create if needed in handler)
+    // User's NFT ATA (receives the NFT on full unlock, created if needed in handler)
     /// CHECK: User NFT ATA (Token-2022)
     #[account(mut)]
     pub user_nft_account: UncheckedAccount<'info>,
 
+    // Receipt NFT (locks with a receipt only, burned on full unlock, metadata refreshed on partial unlock)
+    /// CHECK: Receipt mint
+    #[account(mut, address = lock_account.receipt_mint @ ErrorCode::InvalidReceipt)]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     // Meteora accounts
+    /// CHECK: Pool authority
+    #[account(address = POOL_AUTHORITY)]
+    pub pool_authority: UncheckedAccount<'info>,
     /// CHECK: Pool
-    #[account(mut)]
+    #[account(mut, address = pool_config.pool @ ErrorCode::InvalidPool)]
     pub pool: UncheckedAccount<'info>,
     /// CHECK: Position PDA
-    #[account(mut)]
+    #[account(mut, address = lock_account.position_pda @ ErrorCode::InvalidPosition)]
     pub position: UncheckedAccount<'info>,
     /// CHECK: Token A vault
     #[account(mut)]
@@ -61,20 +114,30 @@ pub struct UnlockLiquidity<'info> {
     /// CHECK: Token B vault
     #[account(mut)]
     pub token_b_vault: UncheckedAccount<'info>,
-    /// CHECK: Token A mint
-    pub token_a_mint: UncheckedAccount<'info>,
-    /// CHECK: Token B mint
-    pub token_b_mint: UncheckedAccount<'info>,
-    /// CHECK: Event authority
+    #[account(
+        address = pool_config.token_a_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_a_program,
+    )]
+    pub token_a_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = pool_config.token_b_mint @ ErrorCode::InvalidMint,
+        mint::token_program = token_b_program,
+    )]
+    pub token_b_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Meteora vesting account (NativeVesting only)
+    #[account(mut, address = lock_account.vesting @ ErrorCode::InvalidVesting)]
+    pub vesting: Option<UncheckedAccount<'info>>,
+    /// CHECK: Meteora event authority
     #[account(
         seeds = [seeds::EVENT_AUTHORITY_SEED],
         bump,
         seeds::program = METEORA_PROGRAM_ID,
     )]
-    pub event_authority: UncheckedAccount<'info>,
+    pub damm_event_authority: UncheckedAccount<'info>,
 
     // Programs
-    pub token_program: Program<'info, token::Token>,  // For SPL tokens (SLERF/USDC)
+    pub token_a_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
+    pub token_b_program: Interface<'info, TokenInterface>,  // SPL Token or Token-2022
     pub token_2022_program: Program<'info, Token2022>,  // For Token-2022 NFT
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
@@ -93,58 +156,82 @@ pub struct UnlockLiquidity<'info> {
 pub fn handle_unlock_liquidity(
     ctx: Context<UnlockLiquidity>,
     liquidity_delta: u128,
+    token_a_min_out: u64,
+    token_b_min_out: u64,
 ) -> Result<()> {
+    // Halted while unlocks are paused globally or for this pool
+    ctx.accounts.config.ensure_not_paused(&ctx.accounts.pool_config, |paused| paused.unlocks)?;
+
+    // Meteora accounts must match the pool and the lock's position
+    Pool::load(&ctx.accounts.pool)?.validate_tokens(
+        &ctx.accounts.token_a_vault.key(),
+        &ctx.accounts.token_b_vault.key(),
+        &ctx.accounts.token_a_mint.key(),
+        &ctx.accounts.token_b_mint.key(),
+    )?;
+    Position::load(&ctx.accounts.position)?.validate(&ctx.accounts.pool.key(), &ctx.accounts.position_nft_mint.key())?;
+
     let now = ctx.accounts.clock.unix_timestamp as u64;
+    let liquidity_locked = ctx.accounts.lock_account.liquidity_locked;
 
-    // Check if lock period has expired
-    if now < ctx.accounts.lock_account.lock_end {
-        return err!(ErrorCode::LockNotExpired);
+    // Authorize the receipt holder (or the recorded owner for locks without a receipt)
+    let receipt_account = ctx.accounts.user_receipt_account.as_deref().map(|receipt| &**receipt);
+    if !ctx.accounts.lock_account.is_owned_by(&ctx.accounts.user.key(), receipt_account) {
+        return err!(ErrorCode::Unauthorized);
     }
 
-    // Validate liquidity_delta
-    if liquidity_delta > ctx.accounts.lock_account.liquidity_locked {
-        return err!(ErrorCode::InvalidUnlockAmount);
-    }
+    // Resolve how much liquidity is withdrawable (0 = everything available)
+    let liquidity_to_remove = match ctx.accounts.lock_account.mode {
+        LockMode::Escrow => {
+            // Check if lock period has expired
+            if now < ctx.accounts.lock_account.lock_end {
+                return err!(ErrorCode::LockNotExpired);
+            }
 
-    let is_full_unlock = liquidity_delta == 0 || liquidity_delta == ctx.accounts.lock_account.liquidity_locked;
+            // Validate liquidity_delta
+            if liquidity_delta > liquidity_locked {
+                return err!(ErrorCode::InvalidUnlockAmount);
+            }
+            if liquidity_delta == 0 { liquidity_locked } else { liquidity_delta }
+        }
+        LockMode::NativeVesting => {
+            let vesting = ctx.accounts.vesting.as_ref().ok_or(ErrorCode::InvalidVesting)?;
 
-    // Create user's NFT ATA if it doesn't exist
-    if ctx.accounts.user_nft_account.owner == &ctx.accounts.system_program.key() {
-        anchor_spl::associated_token::create(CpiContext::new(
-            ctx.accounts.associated_token_program.to_account_info(),
-            anchor_spl::associated_token::Create {
-                payer: ctx.accounts.user.to_account_info(),
-                associated_token: ctx.accounts.user_nft_account.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-                mint: ctx.accounts.position_nft_mint.to_account_info(),
-                system_program: ctx.accounts.system_program.to_account_info(),
-                token_program: ctx.accounts.token_2022_program.to_account_info(),
-            },
-        ))?;
-    }
+            // CPI: Move whatever Meteora has vested into the position's unlocked liquidity
+            let refresh_accounts = RefreshVesting {
+                pool: ctx.accounts.pool.to_account_info(),
+                position: ctx.accounts.position.to_account_info(),
+                position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(),
+                owner: ctx.accounts.escrow_authority.to_account_info(),
+            };
+            refresh_vesting(
+                CpiContext::new(ctx.accounts.damm_program.to_account_info(), refresh_accounts)
+                    .with_remaining_accounts(vec![vesting.to_account_info()]),
+            )?;
 
-    // Calculate escrow bump manually
-    let (_escrow_pda, escrow_bump) = Pubkey::find_program_address(&[seeds::ESCROW_AUTHORITY_SEED], &crate::id());
+            // Only released liquidity can be withdrawn
+            let released = Position::load(&ctx.accounts.position)?.unlocked_liquidity.min(liquidity_locked);
+            let liquidity_to_remove = if liquidity_delta == 0 { released } else { liquidity_delta };
+            if liquidity_to_remove == 0 || liquidity_to_remove > released {
+                return err!(ErrorCode::InvalidUnlockAmount);
+            }
+            liquidity_to_remove
+        }
+        LockMode::Permanent => return err!(ErrorCode::UnsupportedLockMode),
+    };
 
-    // Transfer NFT from escrow to user (Token-2022)
-    anchor_spl::token_interface::transfer_checked(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_2022_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.escrow_nft_account.to_account_info(),
-                to: ctx.accounts.user_nft_account.to_account_info(),
-                authority: ctx.accounts.escrow_authority.to_account_info(),
-                mint: ctx.accounts.position_nft_mint.to_account_info(),
-            },
-            &[&[seeds::ESCROW_AUTHORITY_SEED, &[escrow_bump]]],
-        ),
-        1,   // NFT amount
-        0,   // Decimals for NFT
-    )?;
+    let is_full_unlock = liquidity_to_remove == liquidity_locked;
+    let balance_a_before = ctx.accounts.user_token_a.amount;
+    let balance_b_before = ctx.accounts.user_token_b.amount;
+
+    let lock_account_key = ctx.accounts.lock_account.key();
+    let escrow_bump = ctx.bumps.escrow_authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[seeds::ESCROW_AUTHORITY_SEED, lock_account_key.as_ref(), &[escrow_bump]]];
 
     if is_full_unlock {
         // Full unlock: Use remove_all_liquidity
         let remove_accounts = RemoveAllLiquidity {
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
             pool: ctx.accounts.pool.to_account_info(),
             position: ctx.accounts.position.to_account_info(),
             token_a_account: ctx.accounts.user_token_a.to_account_info(),
@@ -153,22 +240,27 @@ pub fn handle_unlock_liquidity(
             token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
             token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
             token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
-            position_nft_account: ctx.accounts.user_nft_account.to_account_info(), // Use user's ATA
-            owner: ctx.accounts.user.to_account_info(),
-            token_a_program: ctx.accounts.token_program.to_account_info(),
-            token_b_program: ctx.accounts.token_program.to_account_info(),
-            event_authority: ctx.accounts.event_authority.to_account_info(),
+            position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(), // Escrow keeps custody
+            owner: ctx.accounts.escrow_authority.to_account_info(),
+            token_a_program: ctx.accounts.token_a_program.to_account_info(),
+            token_b_program: ctx.accounts.token_b_program.to_account_info(),
+            event_authority: ctx.accounts.damm_event_authority.to_account_info(),
             program: ctx.accounts.damm_program.to_account_info(),
         };
-        remove_all_liquidity(CpiContext::new(ctx.accounts.damm_program.to_account_info(), remove_accounts), u64::MAX, u64::MAX)?;
+        remove_all_liquidity(
+            CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), remove_accounts, signer_seeds),
+            token_a_min_out,
+            token_b_min_out,
+        )?;
     } else {
         // Partial unlock: Use remove_liquidity
         let remove_params = RemoveLiquidityParameters {
-            liquidity_delta,
-            token_a_amount_threshold: u64::MAX,
-            token_b_amount_threshold: u64::MAX,
+            liquidity_delta: liquidity_to_remove,
+            token_a_amount_threshold: token_a_min_out,
+            token_b_amount_threshold: token_b_min_out,
         };
         let remove_accounts = RemoveLiquidity {
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
             pool: ctx.accounts.pool.to_account_info(),
             position: ctx.accounts.position.to_account_info(),
             token_a_account: ctx.accounts.user_token_a.to_account_info(),
@@ -177,23 +269,184 @@ pub fn handle_unlock_liquidity(
             token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
             token_a_mint: ctx.accounts.token_a_mint.to_account_info(),
             token_b_mint: ctx.accounts.token_b_mint.to_account_info(),
-            position_nft_account: ctx.accounts.user_nft_account.to_account_info(), // Use user's ATA
-            owner: ctx.accounts.user.to_account_info(),
-            token_a_program: ctx.accounts.token_program.to_account_info(),
-            token_b_program: ctx.accounts.token_program.to_account_info(),
-            event_authority: ctx.accounts.event_authority.to_account_info(),
+            position_nft_account: ctx.accounts.escrow_nft_account.to_account_info(), // Escrow keeps custody
+            owner: ctx.accounts.escrow_authority.to_account_info(),
+            token_a_program: ctx.accounts.token_a_program.to_account_info(),
+            token_b_program: ctx.accounts.token_b_program.to_account_info(),
+            event_authority: ctx.accounts.damm_event_authority.to_account_info(),
             program: ctx.accounts.damm_program.to_account_info(),
         };
-        remove_liquidity(CpiContext::new(ctx.accounts.damm_program.to_account_info(), remove_accounts), remove_params)?;
+        remove_liquidity(
+            CpiContext::new_with_signer(ctx.accounts.damm_program.to_account_info(), remove_accounts, signer_seeds),
+            remove_params,
+        )?;
+    }
+
+    // Return the NFT only once all liquidity is out; partial unlocks keep it in escrow
+    if is_full_unlock {
+        // Create user's NFT ATA if it doesn't exist
+        if ctx.accounts.user_nft_account.owner == &ctx.accounts.system_program.key() {
+            anchor_spl::associated_token::create(CpiContext::new(
+                ctx.accounts.associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.user.to_account_info(),
+                    associated_token: ctx.accounts.user_nft_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                    mint: ctx.accounts.position_nft_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_2022_program.to_account_info(),
+                },
+            ))?;
+        }
+
+        // Transfer NFT from escrow to user (Token-2022)
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_nft_account.to_account_info(),
+                    to: ctx.accounts.user_nft_account.to_account_info(),
+                    authority: ctx.accounts.escrow_authority.to_account_info(),
+                    mint: ctx.accounts.position_nft_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            1,   // NFT amount
+            0,   // Decimals for NFT
+        )?;
+
+        // CPI: Close the now-empty escrow NFT ATA, rent goes back to the user
+        token_interface::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_interface::CloseAccount {
+                account: ctx.accounts.escrow_nft_account.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.escrow_authority.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        // The receipt is spent together with the lock
+        if ctx.accounts.lock_account.has_receipt() {
+            let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+            let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+            let receipt = ReceiptAccounts {
+                receipt_mint: receipt_mint.to_account_info(),
+                escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+                payer: ctx.accounts.user.to_account_info(),
+                token_program: ctx.accounts.token_2022_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            };
+            receipt.revoke(user_receipt_account.to_account_info(), signer_seeds)?;
+        }
+    }
+
+    ctx.accounts.user_token_a.reload()?;
+    ctx.accounts.user_token_b.reload()?;
+    let token_a_amount = ctx.accounts.user_token_a.amount.saturating_sub(balance_a_before);
+    let token_b_amount = ctx.accounts.user_token_b.amount.saturating_sub(balance_b_before);
+
+    // Skim the protocol fee from the withdrawn tokens
+    let (fee_a, fee_b) = if ctx.accounts.config.fee_sources.on_unlock {
+        (
+            ctx.accounts.pool_config.protocol_fee(token_a_amount),
+            ctx.accounts.pool_config.protocol_fee(token_b_amount),
+        )
+    } else {
+        (0, 0)
+    };
+
+    // Validate slippage on what the user actually keeps
+    if token_a_amount - fee_a < token_a_min_out || token_b_amount - fee_b < token_b_min_out {
+        return err!(ErrorCode::SlippageExceeded);
+    }
+
+    if fee_a > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_a_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_a.to_account_info(),
+                    mint: ctx.accounts.token_a_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_a.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            fee_a,
+            ctx.accounts.token_a_mint.decimals,
+        )?;
+    }
+    if fee_b > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_b_program.to_account_info(),
+                token_interface::TransferChecked {
+                    from: ctx.accounts.user_token_b.to_account_info(),
+                    mint: ctx.accounts.token_b_mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_b.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            fee_b,
+            ctx.accounts.token_b_mint.decimals,
+        )?;
     }
 
     // Update Lock Account
+    ctx.accounts.pool_config.update_incentives(now)?;
     let lock_account = &mut ctx.accounts.lock_account;
+    lock_account.settle(&mut ctx.accounts.pool_config, now)?;
+    ctx.accounts.pool_config.remove_lock(lock_account)?;
+    lock_account.user = ctx.accounts.user.key();  // Ownership follows the receipt
+    lock_account.liquidity_locked -= liquidity_to_remove;
     if is_full_unlock {
-        lock_account.liquidity_locked = 0;
         lock_account.status = LockStatus::Claimed;
+    }
+    ctx.accounts.pool_config.add_lock(lock_account)?;  // Whatever stays locked keeps accruing
+
+    // The receipt outlives a partial unlock, so its metadata follows the remaining liquidity
+    if !is_full_unlock && ctx.accounts.lock_account.has_receipt() {
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(ErrorCode::InvalidReceipt)?;
+        let receipt = ReceiptAccounts {
+            receipt_mint: receipt_mint.to_account_info(),
+            escrow_authority: ctx.accounts.escrow_authority.to_account_info(),
+            payer: ctx.accounts.user.to_account_info(),
+            token_program: ctx.accounts.token_2022_program.to_account_info(),
+            associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        receipt.update_fields(
+            receipt_fields(&ctx.accounts.pool.key(), ctx.accounts.lock_account.liquidity_locked, ctx.accounts.lock_account.lock_end),
+            signer_seeds,
+        )?;
+    }
+
+    if is_full_unlock {
+        emit_cpi!(LiquidityUnlocked {
+            lock_account: ctx.accounts.lock_account.key(),
+            user: ctx.accounts.user.key(),
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            position_nft_mint: ctx.accounts.position_nft_mint.key(),
+            liquidity: liquidity_to_remove,
+            token_a_amount,
+            token_b_amount,
+            timestamp: now,
+        });
     } else {
-        lock_account.liquidity_locked -= liquidity_delta;
+        emit_cpi!(PartialUnlock {
+            lock_account: ctx.accounts.lock_account.key(),
+            user: ctx.accounts.user.key(),
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            position_nft_mint: ctx.accounts.position_nft_mint.key(),
+            liquidity: liquidity_to_remove,
+            liquidity_remaining: ctx.accounts.lock_account.liquidity_locked,
+            token_a_amount,
+            token_b_amount,
+            timestamp: now,
+        });
     }
 
     Ok(())
@@ -1,3 +1,6 @@
+#![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 use instructions::{
     user::{
@@ -5,10 +8,33 @@ use instructions::{
         add_liquidity_ix::*,
         lock_position_ix::*,
         lock_liquidity::*,
+        lock_existing_position::*,
+        lock_forever::*,
+        increase_lock::*,
+        extend_lock::*,
+        transfer_lock::*,
         unlock_liquidity::*,
+        early_unlock::*,
+        close_lock::*,
+        emergency_withdraw::*,
+        claim_locked_rewards::*,
+        claim_locked_fees::*,
+        claim_penalty_rewards::*,
+        claim_incentives::*,
     },
     admin::{
         initialize_config::*,
+        update_config::*,
+        propose_admin::*,
+        accept_admin::*,
+        initialize_pool_config::*,
+        update_pool_config::*,
+        set_duration_tier::*,
+        pause::*,
+        unpause::*,
+        set_emergency_mode::*,
+        fund_incentives::*,
+        set_emission_rate::*,
     }  
 };
 
@@ -16,7 +42,10 @@ pub mod instructions;
 pub mod context;
 pub mod states;
 pub mod errors;
+pub mod events;
 pub mod constants;
+pub mod receipt;
+pub mod math;
 
 declare_id!("DtnLiyCepzKfNiyFHBHEqabhrNe65tx8FPxLWQeh6JeC");
 
@@ -24,16 +53,78 @@ declare_id!("DtnLiyCepzKfNiyFHBHEqabhrNe65tx8FPxLWQeh6JeC");
 pub mod liquidity_locking_program {
     use super::*;
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>, pool_id: Pubkey, fee_bps: u16, slf_mint: Pubkey) -> Result<()> {
-        handle_initialize_config(ctx, pool_id, fee_bps, slf_mint)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_bps: u16,
+        slf_mint: Pubkey,
+        treasury: Pubkey,
+        fee_sources: crate::states::FeeSources,
+    ) -> Result<()> {
+        handle_initialize_config(ctx, fee_bps, slf_mint, treasury, fee_sources)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
+        handle_update_config(ctx, params)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        handle_propose_admin(ctx, new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        handle_accept_admin(ctx)
+    }
+
+    pub fn initialize_pool_config(
+        ctx: Context<InitializePoolConfig>,
+        tiers: Vec<crate::states::DurationTier>,
+        min_lock_duration: u64,
+        max_lock_duration: u64,
+        fee_bps: Option<u16>,
+        reward_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        handle_initialize_pool_config(ctx, tiers, min_lock_duration, max_lock_duration, fee_bps, reward_mint)
+    }
+
+    pub fn update_pool_config(ctx: Context<UpdatePoolConfig>, params: UpdatePoolConfigParams) -> Result<()> {
+        handle_update_pool_config(ctx, params)
+    }
+
+    pub fn set_duration_tier(ctx: Context<SetDurationTier>, index: u8, tier: crate::states::DurationTier) -> Result<()> {
+        handle_set_duration_tier(ctx, index, tier)
+    }
+
+    pub fn pause(ctx: Context<Pause>, flags: crate::states::PauseFlags) -> Result<()> {
+        handle_pause(ctx, flags)
+    }
+
+    pub fn unpause(ctx: Context<Unpause>, flags: crate::states::PauseFlags) -> Result<()> {
+        handle_unpause(ctx, flags)
+    }
+
+    pub fn set_emergency_mode(ctx: Context<SetEmergencyMode>, enabled: bool) -> Result<()> {
+        handle_set_emergency_mode(ctx, enabled)
+    }
+
+    pub fn fund_incentives(ctx: Context<FundIncentives>, amount: u64) -> Result<()> {
+        handle_fund_incentives(ctx, amount)
+    }
+
+    pub fn set_emission_rate(ctx: Context<SetEmissionRate>, emission_rate: u64, emission_end: u64) -> Result<()> {
+        handle_set_emission_rate(ctx, emission_rate, emission_end)
     }
 
     pub fn create_position_ix(ctx: Context<DammV2CreatePosition>) -> Result<()> {
         handle_create_position(ctx)
     }
 
-    pub fn add_liquidity_ix(ctx: Context<DammV2AddLiquidity>, liquidity_delta: u128) -> Result<()> {
-        handle_add_liquidity(ctx, liquidity_delta)
+    pub fn add_liquidity_ix(
+        ctx: Context<DammV2AddLiquidity>,
+        liquidity_delta: u128,
+        token_a_amount_threshold: u64,
+        token_b_amount_threshold: u64,
+    ) -> Result<()> {
+        handle_add_liquidity(ctx, liquidity_delta, token_a_amount_threshold, token_b_amount_threshold)
     }
 
     pub fn lock_position_ix(
@@ -43,11 +134,83 @@ pub mod liquidity_locking_program {
         handle_lock_position(ctx, params)
     }
 
-    pub fn lock_liquidity(ctx: Context<LockLiquidity>, liquidity_delta: u128, duration_months: u8) -> Result<()> {
-        handle_lock_liquidity(ctx, liquidity_delta, duration_months)
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        liquidity_delta: u128,
+        duration: crate::states::LockDuration,
+        token_a_max_in: u64,
+        token_b_max_in: u64,
+        mode: crate::states::LockMode,
+    ) -> Result<()> {
+        handle_lock_liquidity(ctx, liquidity_delta, duration, token_a_max_in, token_b_max_in, mode)
     }
 
-    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>, liquidity_delta: u128) -> Result<()> {
-    handle_unlock_liquidity(ctx, liquidity_delta)
-}
+    pub fn lock_existing_position(
+        ctx: Context<LockExistingPosition>,
+        duration: crate::states::LockDuration,
+    ) -> Result<()> {
+        handle_lock_existing_position(ctx, duration)
+    }
+
+    pub fn lock_forever(ctx: Context<LockForever>) -> Result<()> {
+        handle_lock_forever(ctx)
+    }
+
+    pub fn increase_lock(
+        ctx: Context<IncreaseLock>,
+        liquidity_delta: u128,
+        token_a_max_in: u64,
+        token_b_max_in: u64,
+    ) -> Result<()> {
+        handle_increase_lock(ctx, liquidity_delta, token_a_max_in, token_b_max_in)
+    }
+
+    pub fn extend_lock(ctx: Context<ExtendLock>, duration: crate::states::LockDuration) -> Result<()> {
+        handle_extend_lock(ctx, duration)
+    }
+
+    pub fn transfer_lock(ctx: Context<TransferLock>, new_owner: Pubkey) -> Result<()> {
+        handle_transfer_lock(ctx, new_owner)
+    }
+
+    pub fn unlock_liquidity(
+        ctx: Context<UnlockLiquidity>,
+        liquidity_delta: u128,
+        token_a_min_out: u64,
+        token_b_min_out: u64,
+    ) -> Result<()> {
+        handle_unlock_liquidity(ctx, liquidity_delta, token_a_min_out, token_b_min_out)
+    }
+
+    pub fn early_unlock(
+        ctx: Context<EarlyUnlock>,
+        token_a_min_out: u64,
+        token_b_min_out: u64,
+    ) -> Result<()> {
+        handle_early_unlock(ctx, token_a_min_out, token_b_min_out)
+    }
+
+    pub fn close_lock(ctx: Context<CloseLock>, close_position_account: bool) -> Result<()> {
+        handle_close_lock(ctx, close_position_account)
+    }
+
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        handle_emergency_withdraw(ctx)
+    }
+
+    pub fn claim_locked_rewards(ctx: Context<ClaimLockedRewards>, reward_index: u8) -> Result<()> {
+        handle_claim_locked_rewards(ctx, reward_index)
+    }
+
+    pub fn claim_locked_fees(ctx: Context<ClaimLockedFees>) -> Result<()> {
+        handle_claim_locked_fees(ctx)
+    }
+
+    pub fn claim_penalty_rewards(ctx: Context<ClaimPenaltyRewards>) -> Result<()> {
+        handle_claim_penalty_rewards(ctx)
+    }
+
+    pub fn claim_incentives(ctx: Context<ClaimIncentives>) -> Result<()> {
+        handle_claim_incentives(ctx)
+    }
 }
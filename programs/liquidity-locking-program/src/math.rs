@@ -0,0 +1,237 @@
+use crate::constants::ACCUMULATOR_SCALE_OFFSET;
+
+// The helpers below treat the high half of a U256 as the integer part and the low half as the fraction
+const _: () = assert!(ACCUMULATOR_SCALE_OFFSET == u128::BITS);
+
+// Unsigned 256-bit accumulator value, stored on-chain as little-endian [u8; 32] like DAMM v2's
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Debug)]
+pub struct U256 {
+    hi: u128,
+    lo: u128,
+}
+
+impl U256 {
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        let mut lo = [0u8; 16];
+        let mut hi = [0u8; 16];
+        lo.copy_from_slice(&bytes[..16]);
+        hi.copy_from_slice(&bytes[16..]);
+        Self { hi: u128::from_le_bytes(hi), lo: u128::from_le_bytes(lo) }
+    }
+
+    pub fn to_le_bytes(self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes[..16].copy_from_slice(&self.lo.to_le_bytes());
+        bytes[16..].copy_from_slice(&self.hi.to_le_bytes());
+        bytes
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(other.lo);
+        let hi = self.hi.checked_add(other.hi)?.checked_add(carry as u128)?;
+        Some(Self { hi, lo })
+    }
+
+    pub fn saturating_sub(self, other: Self) -> Self {
+        if self <= other {
+            return Self::default();
+        }
+        let (lo, borrow) = self.lo.overflowing_sub(other.lo);
+        Self { hi: self.hi - other.hi - borrow as u128, lo }
+    }
+
+    // Full 256-bit product of two u128 values
+    pub fn full_mul(a: u128, b: u128) -> Self {
+        Self { hi: mul_hi(a, b), lo: a.wrapping_mul(b) }
+    }
+
+    // self / y, rounded up
+    pub fn div_ceil(self, y: u128) -> Option<Self> {
+        if y == 0 {
+            return None;
+        }
+        // Schoolbook division of the high half, then the low half bit by bit
+        let hi = self.hi / y;
+        let mut rem = self.hi % y;
+        let mut lo = 0u128;
+        for i in (0..u128::BITS).rev() {
+            let carry = rem >> (u128::BITS - 1);
+            rem = (rem << 1) | ((self.lo >> i) & 1);
+            lo <<= 1;
+            if carry == 1 || rem >= y {
+                rem = rem.wrapping_sub(y);
+                lo |= 1;
+            }
+        }
+        Self { hi, lo }.checked_add(Self { hi: 0, lo: (rem != 0) as u128 })
+    }
+
+    // self >> 128, rounded up
+    pub fn shr_128_ceil(self) -> Option<u128> {
+        self.hi.checked_add((self.lo != 0) as u128)
+    }
+
+    pub fn to_u64(self) -> Option<u64> {
+        if self.hi != 0 {
+            return None;
+        }
+        u64::try_from(self.lo).ok()
+    }
+}
+
+// (x << 128) / y, rounded down; the accumulator increment for spreading x over y
+pub fn shl_div_256(x: u128, y: u128) -> Option<U256> {
+    if y == 0 {
+        return None;
+    }
+    // Integer part by plain division, then the fraction of the remainder bit by bit
+    let mut rem = x % y;
+    let mut lo = 0u128;
+    for _ in 0..u128::BITS {
+        let carry = rem >> (u128::BITS - 1);
+        rem <<= 1;
+        lo <<= 1;
+        if carry == 1 || rem >= y {
+            rem = rem.wrapping_sub(y);
+            lo |= 1;
+        }
+    }
+    Some(U256 { hi: x / y, lo })
+}
+
+// (x * y) >> 128, rounded down; what y units earn from an accumulator delta x
+pub fn mul_shr_256(x: U256, y: u128) -> Option<u128> {
+    x.hi.checked_mul(y)?.checked_add(mul_hi(x.lo, y))
+}
+
+// High 128 bits of the 256-bit product a * b, split into 64-bit limbs so nothing overflows
+fn mul_hi(a: u128, b: u128) -> u128 {
+    let mask = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & mask);
+    let (b_hi, b_lo) = (b >> 64, b & mask);
+    let (lo_lo, hi_lo, lo_hi) = (a_lo * b_lo, a_hi * b_lo, a_lo * b_hi);
+    let mid = (lo_lo >> 64) + (hi_lo & mask) + (lo_hi & mask);
+    a_hi * b_hi + (hi_lo >> 64) + (lo_hi >> 64) + (mid >> 64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shl_div_zero() {
+        assert_eq!(shl_div_256(0, 7), Some(U256::default()));
+        assert_eq!(shl_div_256(7, 0), None);
+        assert_eq!(mul_shr_256(U256::default(), u128::MAX), Some(0));
+        assert_eq!(mul_shr_256(U256 { hi: 5, lo: u128::MAX }, 0), Some(0));
+    }
+
+    #[test]
+    fn shl_div_by_one_is_a_plain_shift() {
+        assert_eq!(shl_div_256(1, 1), Some(U256 { hi: 1, lo: 0 }));
+        assert_eq!(shl_div_256(u128::MAX, 1), Some(U256 { hi: u128::MAX, lo: 0 }));
+    }
+
+    #[test]
+    fn shl_div_max_numerator() {
+        // (2^128 - 1) * 2^128 / (2^128 - 1) = 2^128
+        assert_eq!(shl_div_256(u128::MAX, u128::MAX), Some(U256 { hi: 1, lo: 0 }));
+        // (2^128 - 1) * 2^128 / 2 = (2^127 - 1) * 2^128 + 2^127
+        assert_eq!(
+            shl_div_256(u128::MAX, 2),
+            Some(U256 { hi: u128::MAX >> 1, lo: 1 << 127 })
+        );
+    }
+
+    #[test]
+    fn shl_div_full_width_remainder() {
+        // The remainder u128::MAX - 1 needs all 128 bits, so shifting it overflows into the carry
+        let y = u128::MAX;
+        let x = u128::MAX - 1;
+        // (2^128 - 2) * 2^128 / (2^128 - 1) = 2^128 - 2 with remainder 2^128 - 2
+        assert_eq!(shl_div_256(x, y), Some(U256 { hi: 0, lo: u128::MAX - 1 }));
+        // 2^128 / 3 = 0x5555...5 with remainder 1
+        assert_eq!(shl_div_256(1, 3), Some(U256 { hi: 0, lo: u128::MAX / 3 }));
+    }
+
+    #[test]
+    fn mul_shr_max_operands() {
+        assert_eq!(mul_hi(u128::MAX, u128::MAX), u128::MAX - 1);
+        assert_eq!(mul_shr_256(U256 { hi: 0, lo: u128::MAX }, u128::MAX), Some(u128::MAX - 1));
+        assert_eq!(mul_shr_256(U256 { hi: 1, lo: 0 }, u128::MAX), Some(u128::MAX));
+        assert_eq!(mul_shr_256(U256 { hi: 2, lo: 0 }, u128::MAX), None);
+    }
+
+    #[test]
+    fn round_trip_matches_u128_division() {
+        let cases: [(u128, u128); 8] = [
+            (1, 1),
+            (10, 3),
+            (1_000_000, 7),
+            (u64::MAX as u128, 13),
+            (123_456_789_012_345, 1_000_003),
+            (u128::MAX, 3),
+            (u128::MAX / 5, u64::MAX as u128),
+            (u128::MAX, u128::MAX / 2),
+        ];
+        for (x, y) in cases {
+            let per_unit = shl_div_256(x, y).unwrap();
+            // Spread x over y units and pay one unit back: the plain integer quotient
+            assert_eq!(mul_shr_256(per_unit, 1), Some(x / y));
+            // Paying all y units back loses at most one to rounding
+            let paid = mul_shr_256(per_unit, y).unwrap();
+            assert!(paid <= x && x - paid <= 1, "x={x} y={y} paid={paid}");
+        }
+    }
+
+    #[test]
+    fn full_mul_and_div_ceil() {
+        assert_eq!(U256::full_mul(0, u128::MAX), U256::default());
+        assert_eq!(U256::full_mul(u128::MAX, u128::MAX), U256 { hi: u128::MAX - 1, lo: 1 });
+        assert_eq!(U256::full_mul(1 << 64, 1 << 64), U256 { hi: 1, lo: 0 });
+
+        assert_eq!(U256::default().div_ceil(7), Some(U256::default()));
+        assert_eq!(U256 { hi: 3, lo: 5 }.div_ceil(1), Some(U256 { hi: 3, lo: 5 }));
+        assert_eq!(U256 { hi: 0, lo: 10 }.div_ceil(3), Some(U256 { hi: 0, lo: 4 }));
+        assert_eq!(U256 { hi: 0, lo: 9 }.div_ceil(3), Some(U256 { hi: 0, lo: 3 }));
+        assert_eq!(U256 { hi: 1, lo: 0 }.div_ceil(0), None);
+        // (2^128 - 1)^2 / (2^128 - 1) = 2^128 - 1 exactly
+        assert_eq!(
+            U256::full_mul(u128::MAX, u128::MAX).div_ceil(u128::MAX),
+            Some(U256 { hi: 0, lo: u128::MAX })
+        );
+        // A full-width remainder rounds up: (2^256 - 1) / 2^127 = 2^129 - 1 remainder 2^127 - 1
+        assert_eq!(
+            U256 { hi: u128::MAX, lo: u128::MAX }.div_ceil(1 << 127),
+            Some(U256 { hi: 2, lo: 0 })
+        );
+        // Matches u128 arithmetic when everything fits
+        for (x, y) in [(1_000_000_007u128, 13u128), (u128::MAX, 3), (u64::MAX as u128, u64::MAX as u128 - 1)] {
+            assert_eq!(U256 { hi: 0, lo: x }.div_ceil(y), Some(U256 { hi: 0, lo: x.div_ceil(y) }));
+        }
+    }
+
+    #[test]
+    fn shr_128_ceil_and_to_u64() {
+        assert_eq!(U256::default().shr_128_ceil(), Some(0));
+        assert_eq!(U256 { hi: 2, lo: 0 }.shr_128_ceil(), Some(2));
+        assert_eq!(U256 { hi: 2, lo: 1 }.shr_128_ceil(), Some(3));
+        assert_eq!(U256 { hi: u128::MAX, lo: 1 }.shr_128_ceil(), None);
+
+        assert_eq!(U256 { hi: 0, lo: u64::MAX as u128 }.to_u64(), Some(u64::MAX));
+        assert_eq!(U256 { hi: 0, lo: u64::MAX as u128 + 1 }.to_u64(), None);
+        assert_eq!(U256 { hi: 1, lo: 0 }.to_u64(), None);
+    }
+
+    #[test]
+    fn u256_add_sub_and_bytes() {
+        let a = U256 { hi: 1, lo: u128::MAX };
+        let b = U256 { hi: 0, lo: 1 };
+        let sum = a.checked_add(b).unwrap();
+        assert_eq!(sum, U256 { hi: 2, lo: 0 });
+        assert_eq!(sum.saturating_sub(b), a);
+        assert_eq!(b.saturating_sub(a), U256::default());
+        assert_eq!(U256 { hi: u128::MAX, lo: u128::MAX }.checked_add(b), None);
+        assert_eq!(U256::from_le_bytes(a.to_le_bytes()), a);
+    }
+}
@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount, Transfer};
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token_2022::{self, InitializeMint2, MintTo, Burn};
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::Mint as MintState,
+};
+use anchor_spl::token_interface::{
+    metadata_pointer_initialize, MetadataPointerInitialize,
+    mint_close_authority_initialize, MintCloseAuthorityInitialize,
+    non_transferable_mint_initialize, NonTransferableMintInitialize,
+    permanent_delegate_initialize, PermanentDelegateInitialize,
+    token_metadata_initialize, TokenMetadataInitialize,
+    token_metadata_update_field, TokenMetadataUpdateField,
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+};
+use crate::constants::{RECEIPT_NAME, RECEIPT_SYMBOL, RECEIPT_URI};
+
+// Accounts needed to mint, burn and re-issue a lock receipt (Token-2022).
+// The escrow authority is the mint authority, metadata update authority, permanent delegate
+// and close authority.
+pub struct ReceiptAccounts<'info> {
+    pub receipt_mint: AccountInfo<'info>,
+    pub escrow_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> ReceiptAccounts<'info> {
+    // Create the receipt mint with metadata (pool, liquidity, lock_end) stored on the mint itself
+    pub fn create_mint(
+        &self,
+        mint_seeds: &[&[&[u8]]],
+        escrow_seeds: &[&[&[u8]]],
+        transferable: bool,
+        fields: Vec<(String, String)>,
+    ) -> Result<()> {
+        let mut extensions = vec![
+            ExtensionType::MetadataPointer,
+            ExtensionType::PermanentDelegate,
+            ExtensionType::MintCloseAuthority,
+        ];
+        if !transferable {
+            extensions.push(ExtensionType::NonTransferable);
+        }
+        let space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+
+        // Fund the metadata up front; the token program reallocs the mint when it is written
+        let metadata = TokenMetadata {
+            update_authority: OptionalNonZeroPubkey(self.escrow_authority.key()),
+            mint: self.receipt_mint.key(),
+            name: RECEIPT_NAME.to_string(),
+            symbol: RECEIPT_SYMBOL.to_string(),
+            uri: RECEIPT_URI.to_string(),
+            additional_metadata: fields.clone(),
+        };
+        let lamports = Rent::get()?.minimum_balance(space + metadata.tlv_size_of()?);
+
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                self.system_program.clone(),
+                CreateAccount {
+                    from: self.payer.clone(),
+                    to: self.receipt_mint.clone(),
+                },
+                mint_seeds,
+            ),
+            lamports,
+            space as u64,
+            self.token_program.key,
+        )?;
+
+        // Extensions must be initialized before the mint itself
+        metadata_pointer_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                MetadataPointerInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.receipt_mint.clone(),
+                },
+            ),
+            Some(self.escrow_authority.key()),
+            Some(self.receipt_mint.key()),
+        )?;
+        permanent_delegate_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                PermanentDelegateInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.receipt_mint.clone(),
+                },
+            ),
+            self.escrow_authority.key,
+        )?;
+        mint_close_authority_initialize(
+            CpiContext::new(
+                self.token_program.clone(),
+                MintCloseAuthorityInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.receipt_mint.clone(),
+                },
+            ),
+            Some(self.escrow_authority.key),
+        )?;
+        if !transferable {
+            non_transferable_mint_initialize(CpiContext::new(
+                self.token_program.clone(),
+                NonTransferableMintInitialize {
+                    token_program_id: self.token_program.clone(),
+                    mint: self.receipt_mint.clone(),
+                },
+            ))?;
+        }
+
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                self.token_program.clone(),
+                InitializeMint2 { mint: self.receipt_mint.clone() },
+            ),
+            0,   // Decimals
+            self.escrow_authority.key,
+            None,
+        )?;
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.clone(),
+                    metadata: self.receipt_mint.clone(),
+                    update_authority: self.escrow_authority.clone(),
+                    mint_authority: self.escrow_authority.clone(),
+                    mint: self.receipt_mint.clone(),
+                },
+                escrow_seeds,
+            ),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        )?;
+        self.write_fields(fields, escrow_seeds)
+    }
+
+    // Rewrite the metadata fields after the lock changed, topping up rent if the metadata grows
+    pub fn update_fields(&self, fields: Vec<(String, String)>, escrow_seeds: &[&[&[u8]]]) -> Result<()> {
+        let new_len = {
+            let data = self.receipt_mint.try_borrow_data()?;
+            let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+            let mut metadata = mint.get_variable_len_extension::<TokenMetadata>()?;
+            let old_size = metadata.tlv_size_of()?;
+            for (key, value) in &fields {
+                metadata.update(Field::Key(key.clone()), value.clone());
+            }
+            data.len() - old_size + metadata.tlv_size_of()?
+        };
+
+        let required = Rent::get()?.minimum_balance(new_len);
+        let current = self.receipt_mint.lamports();
+        if required > current {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    Transfer {
+                        from: self.payer.clone(),
+                        to: self.receipt_mint.clone(),
+                    },
+                ),
+                required - current,
+            )?;
+        }
+
+        self.write_fields(fields, escrow_seeds)
+    }
+
+    fn write_fields(&self, fields: Vec<(String, String)>, escrow_seeds: &[&[&[u8]]]) -> Result<()> {
+        for (key, value) in fields {
+            token_metadata_update_field(
+                CpiContext::new_with_signer(
+                    self.token_program.clone(),
+                    TokenMetadataUpdateField {
+                        program_id: self.token_program.clone(),
+                        metadata: self.receipt_mint.clone(),
+                        update_authority: self.escrow_authority.clone(),
+                    },
+                    escrow_seeds,
+                ),
+                Field::Key(key),
+                value,
+            )?;
+        }
+        Ok(())
+    }
+
+    // Mint the single receipt token into the owner's Token-2022 ATA (created if needed)
+    pub fn issue(
+        &self,
+        owner: AccountInfo<'info>,
+        receipt_account: AccountInfo<'info>,
+        escrow_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        associated_token::create_idempotent(CpiContext::new(
+            self.associated_token_program.clone(),
+            Create {
+                payer: self.payer.clone(),
+                associated_token: receipt_account.clone(),
+                authority: owner,
+                mint: self.receipt_mint.clone(),
+                system_program: self.system_program.clone(),
+                token_program: self.token_program.clone(),
+            },
+        ))?;
+
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                MintTo {
+                    mint: self.receipt_mint.clone(),
+                    to: receipt_account,
+                    authority: self.escrow_authority.clone(),
+                },
+                escrow_seeds,
+            ),
+            1,   // NFT amount
+        )
+    }
+
+    // Burn the receipt from its holder through the permanent delegate
+    pub fn revoke(&self, receipt_account: AccountInfo<'info>, escrow_seeds: &[&[&[u8]]]) -> Result<()> {
+        token_2022::burn(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                Burn {
+                    mint: self.receipt_mint.clone(),
+                    from: receipt_account,
+                    authority: self.escrow_authority.clone(),
+                },
+                escrow_seeds,
+            ),
+            1,   // NFT amount
+        )
+    }
+}
+
+// Metadata fields describing the lock behind a receipt
+pub fn receipt_fields(pool: &Pubkey, liquidity: u128, lock_end: u64) -> Vec<(String, String)> {
+    vec![
+        ("pool".to_string(), pool.to_string()),
+        ("liquidity".to_string(), liquidity.to_string()),
+        ("lock_end".to_string(), lock_end.to_string()),
+    ]
+}
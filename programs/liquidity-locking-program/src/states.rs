@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+use crate::constants::{BPS_DENOMINATOR, MAX_DURATION_TIERS, CUSTOM_DURATION_TIER, METEORA_PROGRAM_ID, SLOT_DURATION_MS, POOL_STATUS_ENABLED};
+use crate::errors::ErrorCode;
+use crate::math::{shl_div_256, mul_shr_256, U256};
 
 // Define supporting structs from IDL
 #[repr(C)]
@@ -117,6 +121,122 @@ pub struct Position {
     pub padding: [u128; 6],
 }
 
+impl Position {
+    // Deserialize a Meteora-owned Position (owner and discriminator checked)
+    pub fn load(info: &AccountInfo) -> Result<Position> {
+        if info.owner != &METEORA_PROGRAM_ID {
+            return err!(ErrorCode::InvalidPosition);
+        }
+        let data = info.try_borrow_data()?;
+        Position::try_deserialize(&mut &data[..]).map_err(|_| error!(ErrorCode::InvalidPosition))
+    }
+
+    // Position must belong to the pool and to the NFT it is locked under
+    pub fn validate(&self, pool: &Pubkey, nft_mint: &Pubkey) -> Result<()> {
+        if self.pool != *pool {
+            return err!(ErrorCode::InvalidPool);
+        }
+        if self.nft_mint != *nft_mint {
+            return err!(ErrorCode::InvalidPosition);
+        }
+        Ok(())
+    }
+}
+
+impl Pool {
+    pub const DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+
+    // Deserialize a Meteora-owned Pool (owner and discriminator checked)
+    pub fn load(info: &AccountInfo) -> Result<Pool> {
+        if info.owner != &METEORA_PROGRAM_ID {
+            return err!(ErrorCode::InvalidPool);
+        }
+        let data = info.try_borrow_data()?;
+        if data.len() < 8 || data[..8] != Pool::DISCRIMINATOR {
+            return err!(ErrorCode::InvalidPool);
+        }
+        Pool::deserialize(&mut &data[8..]).map_err(|_| error!(ErrorCode::InvalidPool))
+    }
+
+    // Vaults and mints passed alongside the pool must be the pool's own
+    pub fn validate_tokens(
+        &self,
+        token_a_vault: &Pubkey,
+        token_b_vault: &Pubkey,
+        token_a_mint: &Pubkey,
+        token_b_mint: &Pubkey,
+    ) -> Result<()> {
+        if *token_a_vault != self.token_a_vault || *token_b_vault != self.token_b_vault {
+            return err!(ErrorCode::InvalidVault);
+        }
+        if *token_a_mint != self.token_a_mint || *token_b_mint != self.token_b_mint {
+            return err!(ErrorCode::InvalidMint);
+        }
+        Ok(())
+    }
+
+    // Reward vault and mint must belong to the initialized reward slot being claimed
+    pub fn validate_reward(&self, reward_index: u8, reward_vault: &Pubkey, reward_mint: &Pubkey) -> Result<()> {
+        let reward = self.reward_infos.get(reward_index as usize).ok_or(ErrorCode::InvalidRewardMint)?;
+        if reward.initialized == 0 || reward.mint != *reward_mint {
+            return err!(ErrorCode::InvalidRewardMint);
+        }
+        if reward.vault != *reward_vault {
+            return err!(ErrorCode::InvalidVault);
+        }
+        Ok(())
+    }
+
+    // Pool must be enabled and past its activation point (slot or timestamp) to take liquidity
+    pub fn ensure_active(&self, clock: &Clock) -> Result<()> {
+        if self.pool_status != POOL_STATUS_ENABLED {
+            return err!(ErrorCode::PoolDisabled);
+        }
+        if self.current_point(clock) < self.activation_point {
+            return err!(ErrorCode::PoolNotActivated);
+        }
+        Ok(())
+    }
+
+    // Current point in the pool's activation units (slot or timestamp)
+    pub fn current_point(&self, clock: &Clock) -> u64 {
+        if self.activation_type == 0 {
+            clock.slot
+        } else {
+            clock.unix_timestamp as u64
+        }
+    }
+
+    // Token amounts add_liquidity pulls for `liquidity` at the current price, before transfer fees.
+    // Mirrors DAMM v2's rounded-up get_amounts_for_modify_liquidity:
+    // a = L * (sqrt_max - sqrt_price) / (sqrt_price * sqrt_max), b = L * (sqrt_price - sqrt_min) >> 128
+    pub fn liquidity_amounts(&self, liquidity: u128) -> Result<(u64, u64)> {
+        let delta_a = self.sqrt_max_price.checked_sub(self.sqrt_price).ok_or(ErrorCode::MathOverflow)?;
+        let amount_a = U256::full_mul(liquidity, delta_a)
+            .div_ceil(self.sqrt_price)
+            .and_then(|quotient| quotient.div_ceil(self.sqrt_max_price))
+            .and_then(U256::to_u64)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let delta_b = self.sqrt_price.checked_sub(self.sqrt_min_price).ok_or(ErrorCode::MathOverflow)?;
+        let amount_b = U256::full_mul(liquidity, delta_b)
+            .shr_128_ceil()
+            .and_then(|amount| u64::try_from(amount).ok())
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok((amount_a, amount_b))
+    }
+
+    // Convert seconds to the pool's activation units
+    pub fn seconds_to_points(&self, seconds: u64) -> u64 {
+        if self.activation_type == 0 {
+            seconds.saturating_mul(1_000) / SLOT_DURATION_MS
+        } else {
+            seconds
+        }
+    }
+}
+
 // PositionMetrics from IDL
 #[derive(Clone, AnchorSerialize, AnchorDeserialize, Default, Debug)]
 pub struct PositionMetrics {
@@ -167,28 +287,394 @@ pub enum LockStatus {
     Claimed,
 }
 
+// How a lock holds its liquidity
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum LockMode {
+    #[default]
+    Escrow,         // NFT held in escrow, all liquidity released at lock_end
+    NativeVesting,  // NFT held in escrow, liquidity released by a Meteora vesting schedule
+    Permanent,      // NFT held in escrow, liquidity permanently locked in Meteora
+}
+
 // LockAccount PDA for user locks
 #[account]
 pub struct LockAccount {
     pub user: Pubkey,                    // Owner of the lock
     pub position_nft_mint: Pubkey,       // Meteora NFT mint for the position
     pub position_pda: Pubkey,            // Derived Meteora position PDA
+    pub pool: Pubkey,                    // Meteora pool the position belongs to
     pub lock_start: u64,                 // Timestamp when lock started
     pub lock_end: u64,                   // Timestamp when lock ends
     pub liquidity_locked: u128,          // Amount of liquidity added
-    pub duration_months: u8,             // Lock duration (3/6/12)
+    pub duration_seconds: u64,           // Lock duration
+    pub tier_index: u8,                  // Duration tier (CUSTOM_DURATION_TIER for raw seconds)
+    pub reward_multiplier_bps: u16,      // Reward multiplier of the tier (10_000 = 1x, boost dropped once expired)
+    pub early_unlock_penalty_bps: u16,   // Early unlock penalty of the tier (0 = no early unlock)
     pub status: LockStatus,              // Current lock status
+    pub mode: LockMode,                  // Escrow or native Meteora vesting
+    pub vesting: Pubkey,                 // Meteora vesting account (NativeVesting only)
+    pub receipt_mint: Pubkey,            // Token-2022 receipt NFT mint (default if none was issued)
     // New fields for reward vesting
+    pub reward_mint: Pubkey,             // Reward mint at lock time (escrowed rewards stay in this mint)
     pub total_rewards_earned: u64,       // Total SLERF rewards claimed from Meteora
     pub rewards_claimed: u64,            // Total vested SLERF transferred to user
+    pub rewards_vested_base: u64,        // Rewards already vested when the current lock window started
     pub last_claim_time: u64,            // Timestamp of last reward claim (init to lock_start)
+    // Early unlock penalties redistributed to this lock
+    pub penalty_checkpoint_a: [u8; 32],  // Pool penalty_per_liquidity_a at the last settlement (U256)
+    pub penalty_checkpoint_b: [u8; 32],  // Pool penalty_per_liquidity_b at the last settlement (U256)
+    pub penalty_owed_a: u64,             // Settled token A penalties not yet claimed
+    pub penalty_owed_b: u64,             // Settled token B penalties not yet claimed
+    // Program incentives accrued by this lock
+    pub incentive_checkpoint: [u8; 32],  // Pool incentive_per_weight at the last settlement (U256)
+    pub incentives_owed: u64,            // Settled incentives not yet claimed
+}
+
+impl LockAccount {
+    // Rewards released linearly over lock_start..lock_end on top of what vested in earlier windows,
+    // fully vested once the lock expires
+    // (permanent locks never expire, so their rewards are released as soon as they are harvested)
+    pub fn vested_rewards(&self, now: u64) -> u64 {
+        if self.mode == LockMode::Permanent || now >= self.lock_end || self.lock_end <= self.lock_start {
+            return self.total_rewards_earned;
+        }
+        let elapsed = now.saturating_sub(self.lock_start) as u128;
+        let duration = (self.lock_end - self.lock_start) as u128;
+        let unvested = self.total_rewards_earned.saturating_sub(self.rewards_vested_base) as u128;
+        self.rewards_vested_base + (unvested * elapsed / duration) as u64
+    }
+
+    // Start a new lock window at `now` ending at `lock_end`: what has vested so far stays vested,
+    // the rest vests (and the early unlock penalty decays) over the new window
+    pub fn restart_window(&mut self, now: u64, lock_end: u64) {
+        self.rewards_vested_base = self.vested_rewards(now);
+        self.lock_start = now;
+        self.lock_end = lock_end;
+    }
+
+    // Penalty for exiting at `now`: the tier's penalty scaled by the share of the lock still remaining
+    pub fn early_unlock_penalty_at(&self, now: u64) -> u16 {
+        if now >= self.lock_end || self.lock_end <= self.lock_start {
+            return 0;
+        }
+        let remaining = (self.lock_end - now) as u128;
+        let duration = (self.lock_end - self.lock_start) as u128;
+        (self.early_unlock_penalty_bps as u128 * remaining / duration) as u16
+    }
+
+    // Credit penalties redistributed since the last checkpoint; call before liquidity_locked changes
+    pub fn settle_penalties(&mut self, pool_config: &PoolConfig) -> Result<()> {
+        let earned_a = mul_shr_256(
+            U256::from_le_bytes(pool_config.penalty_per_liquidity_a)
+                .saturating_sub(U256::from_le_bytes(self.penalty_checkpoint_a)),
+            self.liquidity_locked,
+        ).ok_or(ErrorCode::MathOverflow)?;
+        let earned_b = mul_shr_256(
+            U256::from_le_bytes(pool_config.penalty_per_liquidity_b)
+                .saturating_sub(U256::from_le_bytes(self.penalty_checkpoint_b)),
+            self.liquidity_locked,
+        ).ok_or(ErrorCode::MathOverflow)?;
+        self.penalty_owed_a = u64::try_from(self.penalty_owed_a as u128 + earned_a).map_err(|_| ErrorCode::MathOverflow)?;
+        self.penalty_owed_b = u64::try_from(self.penalty_owed_b as u128 + earned_b).map_err(|_| ErrorCode::MathOverflow)?;
+        self.penalty_checkpoint_a = pool_config.penalty_per_liquidity_a;
+        self.penalty_checkpoint_b = pool_config.penalty_per_liquidity_b;
+        Ok(())
+    }
+
+    // Incentive weight: liquidity boosted by the tier's reward multiplier
+    pub fn incentive_weight(&self) -> Result<u128> {
+        let boosted = self.liquidity_locked
+            .checked_mul(self.reward_multiplier_bps as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(boosted / BPS_DENOMINATOR as u128)
+    }
+
+    // Credit incentives emitted since the last checkpoint; call after PoolConfig::update_incentives.
+    // An expired lock only earns at 1x: the boost it accrued since the last settlement goes back to
+    // the incentive budget, and its weight in the pool drops to 1x from here on.
+    pub fn settle_incentives(&mut self, pool_config: &mut PoolConfig, now: u64) -> Result<()> {
+        let accrued = U256::from_le_bytes(pool_config.incentive_per_weight)
+            .saturating_sub(U256::from_le_bytes(self.incentive_checkpoint));
+        let boosted = mul_shr_256(accrued, self.incentive_weight()?).ok_or(ErrorCode::MathOverflow)?;
+        let boost_expired = now >= self.lock_end && self.reward_multiplier_bps > BPS_DENOMINATOR as u16;
+        let earned = if boost_expired {
+            mul_shr_256(accrued, self.liquidity_locked).ok_or(ErrorCode::MathOverflow)?
+        } else {
+            boosted
+        };
+        self.incentives_owed = u64::try_from(self.incentives_owed as u128 + earned).map_err(|_| ErrorCode::MathOverflow)?;
+        self.incentive_checkpoint = pool_config.incentive_per_weight;
+
+        if boost_expired {
+            pool_config.incentives_remaining = u64::try_from(pool_config.incentives_remaining as u128 + (boosted - earned))
+                .map_err(|_| ErrorCode::MathOverflow)?;
+            pool_config.remove_lock(self)?;
+            self.reward_multiplier_bps = BPS_DENOMINATOR as u16;
+            pool_config.add_lock(self)?;
+        }
+        Ok(())
+    }
+
+    // Settle everything accrued at the current weight; call before liquidity or multiplier change
+    pub fn settle(&mut self, pool_config: &mut PoolConfig, now: u64) -> Result<()> {
+        self.settle_penalties(pool_config)?;
+        self.settle_incentives(pool_config, now)
+    }
+
+    pub fn has_receipt(&self) -> bool {
+        self.receipt_mint != Pubkey::default()
+    }
+
+    // Active locks with a receipt belong to whoever holds it; otherwise to the recorded user
+    // (the receipt is burned on full unlock, after the holder was recorded as the user)
+    pub fn is_owned_by(&self, user: &Pubkey, receipt_account: Option<&TokenAccount>) -> bool {
+        if !self.has_receipt() || self.status != LockStatus::Active {
+            return self.user == *user;
+        }
+        receipt_account.is_some_and(|receipt| {
+            receipt.mint == self.receipt_mint && receipt.owner == *user && receipt.amount == 1
+        })
+    }
+}
+
+// Which proceeds the protocol fee is charged on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeSources {
+    pub on_fees: bool,                   // Trading fees harvested via claim_locked_fees
+    pub on_rewards: bool,                // Meteora rewards harvested via claim_locked_rewards
+    pub on_unlock: bool,                 // Tokens withdrawn in unlock_liquidity
+}
+
+// Which user actions are halted (globally on Config, per pool on PoolConfig)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PauseFlags {
+    pub locks: bool,                     // lock_liquidity, lock_existing_position, lock_forever
+    pub top_ups: bool,                   // increase_lock, extend_lock
+    pub claims: bool,                    // claim_locked_fees, claim_locked_rewards
+    pub unlocks: bool,                   // unlock_liquidity
+}
+
+impl PauseFlags {
+    // Set (pause) or clear (unpause) every flag that is true in `flags`
+    pub fn apply(&mut self, flags: PauseFlags, paused: bool) {
+        if flags.locks { self.locks = paused; }
+        if flags.top_ups { self.top_ups = paused; }
+        if flags.claims { self.claims = paused; }
+        if flags.unlocks { self.unlocks = paused; }
+    }
 }
 
 // Config PDA for global program settings
 #[account]
 pub struct Config {
-    pub pool_id: Pubkey,                 // Meteora pool ID
     pub admin: Pubkey,                   // Admin pubkey
-    pub fee_bps: u16,                    // Optional program fee in basis points
-    pub slf_mint: Pubkey,                // SLERF mint for rewards
-}
\ No newline at end of file
+    pub fee_bps: u16,                    // Default program fee in basis points for new pools
+    pub slf_mint: Pubkey,                // Default SLERF reward mint for new pools
+    pub treasury: Pubkey,                // Owner of the token accounts receiving protocol fees
+    pub fee_sources: FeeSources,         // Per-source protocol fee toggles
+    pub pending_admin: Pubkey,           // Proposed admin awaiting accept_admin (default = none)
+    pub guardian: Pubkey,                // May pause (not unpause) alongside the admin
+    pub paused: PauseFlags,              // Globally halted actions
+    pub emergency: bool,                 // Meteora declared compromised, emergency_withdraw enabled
+}
+
+impl Config {
+    // An action is halted if it is paused globally or for the lock's pool
+    pub fn ensure_not_paused(&self, pool_config: &PoolConfig, halted: impl Fn(&PauseFlags) -> bool) -> Result<()> {
+        if halted(&self.paused) {
+            return err!(ErrorCode::ProgramPaused);
+        }
+        if halted(&pool_config.paused) {
+            return err!(ErrorCode::PoolPaused);
+        }
+        Ok(())
+    }
+
+    pub fn can_pause(&self, authority: &Pubkey) -> bool {
+        *authority == self.admin || (*authority == self.guardian && self.guardian != Pubkey::default())
+    }
+}
+
+// Admin-managed lock duration tier
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct DurationTier {
+    pub duration_seconds: u64,           // Lock duration
+    pub label: [u8; 16],                 // UTF-8 display label, zero padded (e.g. "12M")
+    pub reward_multiplier_bps: u16,      // Reward multiplier (10_000 = 1x)
+    pub enabled: bool,                   // Whether new locks may use this tier
+    pub early_unlock_penalty_bps: u16,   // Penalty for exiting right after locking (0 = no early unlock)
+}
+
+// How a top-up moves lock_end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum TopUpPolicy {
+    #[default]
+    Keep,    // lock_end is unchanged
+    Reset,   // lock_end restarts from now for the lock's duration (never shortened)
+    Extend,  // lock_end is pushed out by the duration, weighted by the added liquidity share
+}
+
+// Requested lock duration, either a configured tier or raw seconds within the pool bounds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum LockDuration {
+    Tier(u8),
+    Seconds(u64),
+}
+
+// Lock duration resolved against a PoolConfig
+pub struct ResolvedDuration {
+    pub duration_seconds: u64,
+    pub tier_index: u8,
+    pub reward_multiplier_bps: u16,
+    pub early_unlock_penalty_bps: u16,
+}
+
+// PoolConfig PDA for per-pool settings
+#[account]
+pub struct PoolConfig {
+    pub pool: Pubkey,                                 // Meteora DAMM v2 pool address
+    pub tiers: [DurationTier; MAX_DURATION_TIERS],    // Lock duration tiers
+    pub min_lock_duration: u64,                       // Minimum raw lock duration in seconds
+    pub max_lock_duration: u64,                       // Maximum raw lock duration in seconds
+    pub fee_bps: u16,                                 // Program fee in basis points
+    pub reward_mint: Pubkey,                          // Reward mint claimed for locks in this pool
+    pub token_a_mint: Pubkey,                         // Pool token A mint (SPL Token or Token-2022)
+    pub token_b_mint: Pubkey,                         // Pool token B mint (SPL Token or Token-2022)
+    pub top_up_policy: TopUpPolicy,                   // How increase_lock moves lock_end
+    pub receipt_transferable: bool,                   // Whether lock receipts can be transferred
+    pub paused: PauseFlags,                           // Actions halted for this pool
+    pub total_liquidity_locked: u128,                 // Liquidity of all active locks in this pool
+    pub penalty_per_liquidity_a: [u8; 32],            // Redistributed token A penalties per liquidity (U256)
+    pub penalty_per_liquidity_b: [u8; 32],            // Redistributed token B penalties per liquidity (U256)
+    pub total_incentive_weight: u128,                 // Incentive weight of all active locks in this pool
+    pub incentive_per_weight: [u8; 32],               // Emitted incentives per weight (U256)
+    pub emission_rate: u64,                           // Incentive tokens emitted per second
+    pub emission_end: u64,                            // Timestamp emissions stop
+    pub last_emission_time: u64,                      // Timestamp incentive_per_weight was last brought up to date
+    pub incentives_remaining: u64,                    // Funded incentives not yet emitted
+    pub incentive_mint: Pubkey,                       // Incentive mint, fixed by the first funding (default until then)
+    pub bump: u8,                                     // PDA bump
+}
+
+impl PoolConfig {
+    // Validate a requested duration and look up its reward multiplier
+    pub fn resolve_duration(&self, duration: LockDuration) -> Result<ResolvedDuration> {
+        match duration {
+            LockDuration::Tier(index) => {
+                let tier = self.tiers.get(index as usize).ok_or(ErrorCode::InvalidTier)?;
+                if !tier.enabled || tier.duration_seconds == 0 {
+                    return err!(ErrorCode::InvalidTier);
+                }
+                Ok(ResolvedDuration {
+                    duration_seconds: tier.duration_seconds,
+                    tier_index: index,
+                    reward_multiplier_bps: tier.reward_multiplier_bps,
+                    early_unlock_penalty_bps: tier.early_unlock_penalty_bps,
+                })
+            }
+            LockDuration::Seconds(seconds) => {
+                if seconds == 0 || seconds < self.min_lock_duration || seconds > self.max_lock_duration {
+                    return err!(ErrorCode::InvalidDuration);
+                }
+                // Raw durations earn the multiplier (and carry the penalty) of the longest enabled tier they cover
+                let covered = self.tiers
+                    .iter()
+                    .filter(|tier| tier.enabled && tier.duration_seconds > 0 && tier.duration_seconds <= seconds)
+                    .max_by_key(|tier| tier.duration_seconds);
+                Ok(ResolvedDuration {
+                    duration_seconds: seconds,
+                    tier_index: CUSTOM_DURATION_TIER,
+                    reward_multiplier_bps: covered.map_or(BPS_DENOMINATOR as u16, |tier| tier.reward_multiplier_bps),
+                    early_unlock_penalty_bps: covered.map_or(0, |tier| tier.early_unlock_penalty_bps),
+                })
+            }
+        }
+    }
+
+    // Highest multiplier among enabled tiers (1x when none are enabled)
+    pub fn max_reward_multiplier_bps(&self) -> u16 {
+        self.tiers
+            .iter()
+            .filter(|tier| tier.enabled)
+            .map(|tier| tier.reward_multiplier_bps)
+            .max()
+            .unwrap_or(BPS_DENOMINATOR as u16)
+    }
+
+    // Count a lock's liquidity and incentive weight towards the pool totals
+    pub fn add_lock(&mut self, lock: &LockAccount) -> Result<()> {
+        self.total_liquidity_locked = self.total_liquidity_locked
+            .checked_add(lock.liquidity_locked)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.total_incentive_weight = self.total_incentive_weight
+            .checked_add(lock.incentive_weight()?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove_lock(&mut self, lock: &LockAccount) -> Result<()> {
+        self.total_liquidity_locked = self.total_liquidity_locked.saturating_sub(lock.liquidity_locked);
+        self.total_incentive_weight = self.total_incentive_weight.saturating_sub(lock.incentive_weight()?);
+        Ok(())
+    }
+
+    // Emit incentives for the time since the last update, bounded by what has been funded.
+    // Nothing is emitted (or spent) while no lock carries weight.
+    pub fn update_incentives(&mut self, now: u64) -> Result<()> {
+        let until = now.min(self.emission_end);
+        if until > self.last_emission_time && self.total_incentive_weight > 0 {
+            let elapsed = (until - self.last_emission_time) as u128;
+            let emitted = (self.emission_rate as u128 * elapsed).min(self.incentives_remaining as u128) as u64;
+            let per_weight = shl_div_256(emitted as u128, self.total_incentive_weight).ok_or(ErrorCode::MathOverflow)?;
+            self.incentive_per_weight = U256::from_le_bytes(self.incentive_per_weight)
+                .checked_add(per_weight)
+                .ok_or(ErrorCode::MathOverflow)?
+                .to_le_bytes();
+            // Only what the accumulator can pay out is spent; the rounding remainder is emitted later
+            let distributed = mul_shr_256(per_weight, self.total_incentive_weight).ok_or(ErrorCode::MathOverflow)?;
+            self.incentives_remaining -= distributed as u64;
+        }
+        self.last_emission_time = self.last_emission_time.max(now);
+        Ok(())
+    }
+
+    // Spread penalties over the liquidity still locked; callers route them elsewhere when none is
+    pub fn distribute_penalties(&mut self, amount_a: u64, amount_b: u64) -> Result<()> {
+        let per_liquidity_a = shl_div_256(amount_a as u128, self.total_liquidity_locked).ok_or(ErrorCode::MathOverflow)?;
+        let per_liquidity_b = shl_div_256(amount_b as u128, self.total_liquidity_locked).ok_or(ErrorCode::MathOverflow)?;
+        self.penalty_per_liquidity_a = U256::from_le_bytes(self.penalty_per_liquidity_a)
+            .checked_add(per_liquidity_a)
+            .ok_or(ErrorCode::MathOverflow)?
+            .to_le_bytes();
+        self.penalty_per_liquidity_b = U256::from_le_bytes(self.penalty_per_liquidity_b)
+            .checked_add(per_liquidity_b)
+            .ok_or(ErrorCode::MathOverflow)?
+            .to_le_bytes();
+        Ok(())
+    }
+
+    // Protocol fee owed on `amount`, rounded down
+    pub fn protocol_fee(&self, amount: u64) -> u64 {
+        (amount as u128 * self.fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    // New lock_end after a top-up of liquidity_added, per the pool's policy
+    pub fn top_up_lock_end(&self, lock: &LockAccount, liquidity_added: u128, now: u64) -> Result<u64> {
+        match self.top_up_policy {
+            TopUpPolicy::Keep => Ok(lock.lock_end),
+            TopUpPolicy::Reset => {
+                let reset_end = now.checked_add(lock.duration_seconds).ok_or(ErrorCode::MathOverflow)?;
+                Ok(reset_end.max(lock.lock_end))
+            }
+            TopUpPolicy::Extend => {
+                let total = lock.liquidity_locked.checked_add(liquidity_added).ok_or(ErrorCode::MathOverflow)?;
+                let extension = (lock.duration_seconds as u128)
+                    .checked_mul(liquidity_added)
+                    .ok_or(ErrorCode::MathOverflow)?
+                    / total;
+                let base = lock.lock_end.max(now);
+                Ok(base.checked_add(extension as u64).ok_or(ErrorCode::MathOverflow)?)
+            }
+        }
+    }
+}
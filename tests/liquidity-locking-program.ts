@@ -6,13 +6,22 @@ import {
   Keypair,
   PublicKey,
   ComputeBudgetProgram,
+  SystemProgram,
   Transaction,
+  TransactionInstruction,
   SYSVAR_CLOCK_PUBKEY,
 } from "@solana/web3.js";
 import {
   createAssociatedTokenAccountInstruction,
+  createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
+  createMintToInstruction,
+  ExtensionType,
   getAccount,
   getAssociatedTokenAddress,
+  getAssociatedTokenAddressSync,
+  getMintLen,
+  getTokenMetadata,
   TOKEN_PROGRAM_ID,
   TOKEN_2022_PROGRAM_ID,
 } from "@solana/spl-token";
@@ -24,6 +33,7 @@ import {
   derivePositionNftAccount,
 } from "@meteora-ag/cp-amm-sdk";
 import BN from "bn.js";
+import { assert } from "chai";
 
 config({ path: "./tests/.env" });
 
@@ -104,9 +114,167 @@ describe("liquidity-locking-program", () => {
     await new Promise((r) => setTimeout(r, 500));
   };
 
+  // Expect an instruction to fail with the given program error code
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+    } catch (err) {
+      assert.equal((err as anchor.AnchorError).error?.errorCode?.code, code);
+      return;
+    }
+    assert.fail(`Expected ${code}`);
+  };
+
+  // Anchor's tag for emit_cpi! self-invocations (EVENT_IX_TAG, little-endian)
+  const EVENT_IX_TAG = Buffer.from("e445a52e51cb9a1d", "hex");
+
+  // Events a transaction emitted through emit_cpi!, decoded from its inner instructions
+  const fetchCpiEvents = async (signature: string) => {
+    await connection.confirmTransaction(signature, "confirmed");
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const keys = tx!.transaction.message.getAccountKeys({
+      accountKeysFromLookups: tx!.meta!.loadedAddresses,
+    });
+    return tx!.meta!.innerInstructions!.flatMap((inner) =>
+      inner.instructions
+        .filter((ix) => keys.get(ix.programIdIndex)!.equals(program.programId))
+        .map((ix) => Buffer.from(bs58.decode(ix.data)))
+        .filter((data) => data.subarray(0, 8).equals(EVENT_IX_TAG))
+        .map((data) =>
+          program.coder.events.decode(data.subarray(8).toString("base64"))
+        )
+        .filter((event) => event !== null)
+    );
+  };
+
+  // Receipt mint of a lock
+  const deriveReceiptMint = (lock: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), lock.toBuffer()],
+      program.programId
+    )[0];
+
+  // Token-2022 receipt ATA of `owner` for a lock
+  const deriveReceiptAccount = (lock: PublicKey, owner: PublicKey) =>
+    getAssociatedTokenAddressSync(
+      deriveReceiptMint(lock),
+      owner,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+  // Receipt metadata field of a lock (e.g. "liquidity", "lock_end")
+  const fetchReceiptField = async (lock: PublicKey, key: string) => {
+    const metadata = await getTokenMetadata(
+      connection,
+      deriveReceiptMint(lock),
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    return metadata?.additionalMetadata.find(([k]) => k === key)?.[1];
+  };
+
+  // Create a position owned by the user and add liquidity to it via the passthroughs
+  const createFundedPosition = async (
+    pool: PublicKey,
+    liquidityDelta: BN
+  ): Promise<Keypair> => {
+    const nftMint = Keypair.generate();
+    const position = derivePositionAddress(nftMint.publicKey);
+    const positionNftAccount = derivePositionNftAccount(nftMint.publicKey);
+
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const tokenAAccount = await getAssociatedTokenAddress(
+      poolState.tokenAMint,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const tokenBAccount = await getAssociatedTokenAddress(
+      poolState.tokenBMint,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+
+    const createTx = await program.methods
+      .createPositionIx()
+      .accounts({
+        owner: user,
+        positionNftMint: nftMint.publicKey,
+        positionNftAccount,
+        pool,
+        position,
+        poolAuthority: new PublicKey(
+          "HLnpSz9h2S4hiLQ43rnSD9XkcUThA7B8hQMKmDaiTLcC"
+        ),
+        payer: user,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        eventAuthority,
+        dammProgram: METEORA_PROGRAM_ID,
+      })
+      .preInstructions([computeUnitIx])
+      .signers([userKeypair, nftMint])
+      .rpc();
+    logTxnSignature(createTx);
+
+    const maxTokenIn = new BN(10_000_000);
+    const addTx = await program.methods
+      .addLiquidityIx(liquidityDelta, maxTokenIn, maxTokenIn)
+      .accounts({
+        pool,
+        position,
+        tokenAAccount,
+        tokenBAccount,
+        tokenAVault: poolState.tokenAVault,
+        tokenBVault: poolState.tokenBVault,
+        tokenAMint: poolState.tokenAMint,
+        tokenBMint: poolState.tokenBMint,
+        positionNftAccount,
+        owner: user,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        eventAuthority,
+        dammProgram: METEORA_PROGRAM_ID,
+      })
+      .preInstructions([computeUnitIx])
+      .signers([userKeypair])
+      .rpc();
+    logTxnSignature(addTx);
+
+    return nftMint;
+  };
+
+  // Liquidity bought by depositing up to `amount` of each token at the current price
+  // (real DAMM v2 liquidity, including its 2^64 factor)
+  const liquidityForDeposit = async (pool: PublicKey, amount: BN) => {
+    const poolState = await cpAmm.fetchPoolState(pool);
+    return cpAmm.getLiquidityDelta({
+      maxAmountTokenA: amount,
+      maxAmountTokenB: amount,
+      sqrtPrice: poolState.sqrtPrice,
+      sqrtMinPrice: poolState.sqrtMinPrice,
+      sqrtMaxPrice: poolState.sqrtMaxPrice,
+    });
+  };
+
   // Shared variables for sequential tests
   let positionNftMint: Keypair;
   let lockAccount: PublicKey;
+  let existingNftMint: Keypair;
+  let permanentNftMint: Keypair;
 
   before(async () => {
     const lamports = 100 * 10 ** 9; // 100 SOL
@@ -139,15 +307,24 @@ describe("liquidity-locking-program", () => {
       TOKEN_PROGRAM_ID.toBase58(), // tokenProgram
     ]).catch((err) => console.log("Error setting USDC balance", err));
 
+    // Treasury (admin) token accounts for protocol fees
+    for (const mint of [SLERF_MINT, USDC_MINT]) {
+      await rpcCall("surfnet_setTokenAccount", [
+        admin.toBase58(), // owner
+        mint.toBase58(), // mint
+        { amount: 0 }, // empty account
+        TOKEN_PROGRAM_ID.toBase58(), // tokenProgram
+      ]).catch((err) => console.log("Error creating treasury account", err));
+    }
+
     console.log("User funded with SOL, SLERF, and USDC");
   });
 
   it("Initialize Config", async () => {
-    const poolId = new PublicKey(
-      "8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ"
-    ); // SLERF-USDC pool
     const feeBps = 50; // 0.5% fee
     const slfMint = SLERF_MINT;
+    const treasury = admin;
+    const feeSources = { onFees: true, onRewards: true, onUnlock: true };
 
     const [configPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("config")],
@@ -161,7 +338,7 @@ describe("liquidity-locking-program", () => {
     });
 
     const tx = await program.methods
-      .initializeConfig(poolId, feeBps, slfMint)
+      .initializeConfig(feeBps, slfMint, treasury, feeSources)
       .accounts({
         admin: user,
       })
@@ -176,6 +353,145 @@ describe("liquidity-locking-program", () => {
     console.log("Config initialized:", configAccount);
   });
 
+  it("Update Config", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    const tx = await program.methods
+      .updateConfig({
+        feeBps: 30, // 0.3% fee
+        slfMint: null,
+        treasury: null,
+        feeSources: null,
+        guardian: null,
+      })
+      .accounts({
+        admin: user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const configAccount = await program.account.config.fetch(configPda);
+    console.log("Config fee bps:", configAccount.feeBps);
+  });
+
+  it("Rotate Admin", async () => {
+    const [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+
+    // Step 1: current admin proposes the new admin
+    const proposeTx = await program.methods
+      .proposeAdmin(admin)
+      .accounts({
+        admin: user,
+      })
+      .signers([userKeypair])
+      .rpc();
+    logTxnSignature(proposeTx);
+
+    // Step 2: proposed admin accepts
+    const acceptTx = await program.methods
+      .acceptAdmin()
+      .accounts({
+        newAdmin: admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(acceptTx);
+
+    const configAccount = await program.account.config.fetch(configPda);
+    console.log("Config admin:", configAccount.admin.toBase58());
+  });
+
+  it("Initialize Pool Config", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ"); // SLERF-USDC pool
+    const month = 30 * 24 * 60 * 60;
+
+    // Duration tiers: 1/3/6/12/24 months with increasing reward multipliers
+    // and early unlock penalties
+    const tier = (
+      months: number,
+      label: string,
+      multiplierBps: number,
+      penaltyBps: number
+    ) => {
+      const labelBytes = Buffer.alloc(16);
+      labelBytes.write(label);
+      return {
+        durationSeconds: new BN(months * month),
+        label: Array.from(labelBytes),
+        rewardMultiplierBps: multiplierBps,
+        enabled: true,
+        earlyUnlockPenaltyBps: penaltyBps,
+      };
+    };
+    const tiers = [
+      tier(1, "1M", 10_000, 1_000),
+      tier(3, "3M", 12_500, 2_000),
+      tier(6, "6M", 15_000, 3_000),
+      tier(12, "12M", 20_000, 4_000),
+      tier(24, "24M", 30_000, 5_000),
+    ];
+    const minLockDuration = new BN(month);
+    const maxLockDuration = new BN(24 * month);
+
+    const [poolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_config"), pool.toBuffer()],
+      program.programId
+    );
+    console.log("Pool Config PDA:", poolConfigPda.toBase58());
+
+    // Multipliers must stay within 1x..=5x
+    await expectError(
+      program.methods
+        .initializePoolConfig(
+          [...tiers, tier(36, "36M", 9_999, 5_000)],
+          minLockDuration,
+          maxLockDuration,
+          null,
+          null
+        )
+        .accounts({ pool, admin })
+        .signers([adminKeypair])
+        .rpc(),
+      "InvalidMultiplier"
+    );
+
+    // Fee and reward mint fall back to the global config defaults
+    const tx = await program.methods
+      .initializePoolConfig(tiers, minLockDuration, maxLockDuration, null, null)
+      .accounts({
+        pool,
+        admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const poolConfigAccount = await program.account.poolConfig.fetch(
+      poolConfigPda
+    );
+    console.log("Pool config initialized:", poolConfigAccount);
+
+    // Tier updates are held to the same multiplier bounds
+    const setTier = (multiplierBps: number) =>
+      program.methods
+        .setDurationTier(4, tier(24, "24M", multiplierBps, 5_000))
+        .accounts({ poolConfig: poolConfigPda, admin })
+        .signers([adminKeypair])
+        .rpc();
+    await expectError(setTier(50_001), "InvalidMultiplier");
+    await expectError(setTier(0), "InvalidMultiplier");
+    logTxnSignature(await setTier(30_000));
+  });
+
   it.skip("Create Position", async () => {
     const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ"); // SLERF-USDC pool
 
@@ -297,8 +613,11 @@ describe("liquidity-locking-program", () => {
       units: 400_000,
     });
 
+    // Max tokens willing to deposit on each side
+    const maxTokenIn = new BN(10_000_000);
+
     const tx = await program.methods
-      .addLiquidityIx(liquidityDelta)
+      .addLiquidityIx(liquidityDelta, maxTokenIn, maxTokenIn)
       .accounts({
         pool, // #1
         position, // #2
@@ -405,6 +724,94 @@ describe("liquidity-locking-program", () => {
     logTxnSignature(tx);
   });
 
+  it("Pause and Unpause", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const [poolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_config"), pool.toBuffer()],
+      program.programId
+    );
+    const flags = { locks: true, topUps: true, claims: false, unlocks: false };
+
+    // Halt new locks and top-ups for the pool only
+    const pauseTx = await program.methods
+      .pause(flags)
+      .accounts({
+        poolConfig: poolConfigPda,
+        authority: admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(pauseTx);
+
+    const paused = await program.account.poolConfig.fetch(poolConfigPda);
+    console.log("Pool paused flags:", paused.paused);
+
+    const unpauseTx = await program.methods
+      .unpause(flags)
+      .accounts({
+        poolConfig: poolConfigPda,
+        admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(unpauseTx);
+  });
+
+  it("Fund Incentives", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const [poolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_config"), pool.toBuffer()],
+      program.programId
+    );
+
+    // Give the admin SLERF to fund the pool's incentive budget
+    const fundAmount = new BN(1_000_000).mul(new BN(10).pow(new BN(9))); // 1,000,000 SLERF
+    await rpcCall("surfnet_setTokenAccount", [
+      admin.toBase58(), // owner
+      SLERF_MINT.toBase58(), // mint
+      { amount: fundAmount.toNumber() },
+      TOKEN_PROGRAM_ID.toBase58(), // tokenProgram
+    ]).catch((err) => console.log("Error setting admin SLERF balance", err));
+    const adminTokenAccount = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const fundTx = await program.methods
+      .fundIncentives(fundAmount)
+      .accounts({
+        poolConfig: poolConfigPda,
+        adminTokenAccount,
+        incentiveMint: SLERF_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(fundTx);
+
+    // Emit 1 SLERF per second for a year, weighted by liquidity x tier multiplier
+    const info = await connection.getAccountInfo(SYSVAR_CLOCK_PUBKEY);
+    const now = Number(info!.data.readBigInt64LE(32));
+    const rateTx = await program.methods
+      .setEmissionRate(new BN(10 ** 9), new BN(now + 365 * 24 * 60 * 60))
+      .accounts({
+        poolConfig: poolConfigPda,
+        admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(rateTx);
+
+    const poolConfig = await program.account.poolConfig.fetch(poolConfigPda);
+    console.log("Incentives remaining:", poolConfig.incentivesRemaining.toString());
+
+    // The first funding pins the pool's incentive mint
+    assert.isTrue(poolConfig.incentiveMint.equals(SLERF_MINT));
+  });
+
   it("Lock Liquidity", async () => {
     // Generate positionNftMint here (since lock_liquidity creates it)
     positionNftMint = Keypair.generate();
@@ -412,7 +819,7 @@ describe("liquidity-locking-program", () => {
 
     const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
     const liquidityDelta = new BN(100); // Match add liquidity amount
-    const durationMonths = 3;
+    const duration = { tier: { 0: 1 } }; // 3 month tier
 
     // Derive accounts
     const position = derivePositionAddress(positionNftMint.publicKey);
@@ -438,18 +845,26 @@ describe("liquidity-locking-program", () => {
 
     // Derive LockAccount PDA
     [lockAccount] = PublicKey.findProgramAddressSync(
-      [
-        Buffer.from("lock"),
-        user.toBuffer(),
-        positionNftMint.publicKey.toBuffer(),
-      ],
+      [Buffer.from("lock"), positionNftMint.publicKey.toBuffer()],
       program.programId
     );
     console.log("Lock Account PDA:", lockAccount.toBase58());
 
+    // Receipt NFT minted to the user (Token-2022)
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const userReceiptAccount = await getAssociatedTokenAddress(
+      receiptMint,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
     // Escrow Authority PDA
     const [escrowAuthority] = PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow_authority")],
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
       program.programId
     );
     console.log("Escrow Authority PDA:", escrowAuthority.toBase58());
@@ -484,8 +899,11 @@ describe("liquidity-locking-program", () => {
       units: 400_000,
     });
 
+    // Max tokens willing to deposit on each side
+    const maxTokenIn = new BN(10_000_000);
+
     const tx = await program.methods
-      .lockLiquidity(liquidityDelta, durationMonths)
+      .lockLiquidity(liquidityDelta, duration, maxTokenIn, maxTokenIn, { escrow: {} })
       .accounts({
         userTokenA,
         userTokenB,
@@ -500,6 +918,9 @@ describe("liquidity-locking-program", () => {
         tokenBMint,
         tokenAProgram: TOKEN_PROGRAM_ID, // For SLERF (SPL Token)
         tokenBProgram: TOKEN_PROGRAM_ID, // For USDC (SPL Token)
+        vesting: null, // Only used by native vesting locks
+        receiptMint,
+        userReceiptAccount,
         user, // User
       })
       .preInstructions([computeUnitIx])
@@ -508,46 +929,59 @@ describe("liquidity-locking-program", () => {
 
     logTxnSignature(tx);
 
+    const receipt = await getAccount(
+      connection,
+      userReceiptAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    console.log("Receipt balance:", receipt.amount.toString());
+
     // Optional: Fetch and log lock account
     const lockData = await program.account.lockAccount.fetch(lockAccount);
     console.log("Lock Account:", lockData);
-  });
 
-  it("Unlock Liquidity", async () => {
-    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
-    const liquidityDelta = new BN(0); // 0 for full unlock
-
-    // Skip time to expire the lock (3 months in seconds)
-    await skipTime(3 * 30 * 24 * 60 * 60);
+    // Indexers see the lock through a LiquidityLocked event
+    const events = await fetchCpiEvents(tx);
+    const locked = events.find((event) => event.name === "liquidityLocked");
+    assert.isDefined(locked, "No LiquidityLocked event");
+    assert.isTrue(locked!.data.lockAccount.equals(lockAccount));
+    assert.isTrue(locked!.data.user.equals(user));
+    assert.isTrue(locked!.data.pool.equals(pool));
+    assert.isTrue(locked!.data.positionNftMint.equals(positionNftMint.publicKey));
+    assert.equal(locked!.data.liquidity.toString(), liquidityDelta.toString());
+    assert.equal(locked!.data.lockEnd.toString(), lockData.lockEnd.toString());
+    assert.isTrue(locked!.data.receiptMint.equals(receiptMint));
+  });
 
-    // Derive accounts (reuse from lock test)
-    const positionAddress = derivePositionAddress(positionNftMint.publicKey);
-    console.log("Derived Position Address:", positionAddress.toBase58());
-    const positionNftAccount = derivePositionNftAccount(
-      positionNftMint.publicKey
+  it("Reject Forged Events", async () => {
+    // Only the program itself can invoke its event instruction (the event
+    // authority PDA must sign), so events cannot be spoofed from outside
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      program.programId
     );
-    console.log("Derived Position NFT Account:", positionNftAccount.toBase58());
+    const forged = new TransactionInstruction({
+      programId: program.programId,
+      keys: [{ pubkey: eventAuthority, isSigner: false, isWritable: false }],
+      data: Buffer.concat([EVENT_IX_TAG, Buffer.alloc(8)]),
+    });
+    await expectError(
+      provider
+        .sendAndConfirm(new Transaction().add(forged), [userKeypair])
+        .catch((err) => {
+          throw anchor.AnchorError.parse(err.logs) ?? err;
+        }),
+      "ConstraintSigner"
+    );
+  });
 
-    // Debug: Check pool and position discriminators
-    const poolInfo = await program.provider.connection.getAccountInfo(pool);
-    if (poolInfo) {
-      console.log("Pool owner:", poolInfo.owner.toBase58());
-      console.log("Pool discriminator:", poolInfo.data.slice(0, 8));
-    } else {
-      console.log("Pool not found");
-    }
+  it("Increase Lock", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const liquidityDelta = new BN(50); // Top-up amount
 
-    const positionInfo = await program.provider.connection.getAccountInfo(
-      positionAddress
-    );
-    if (positionInfo) {
-      console.log("Position owner:", positionInfo.owner.toBase58());
-      console.log("Position discriminator:", positionInfo.data.slice(0, 8));
-    } else {
-      console.log("Position not found");
-    }
+    const position = derivePositionAddress(positionNftMint.publicKey);
 
-    // User's token ATAs (reuse from lock test)
     const userTokenA = await getAssociatedTokenAddress(
       SLERF_MINT,
       user,
@@ -561,14 +995,10 @@ describe("liquidity-locking-program", () => {
       TOKEN_PROGRAM_ID
     );
 
-    // Escrow Authority PDA (reuse from lock test)
     const [escrowAuthority] = PublicKey.findProgramAddressSync(
-      [Buffer.from("escrow_authority")],
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
       program.programId
     );
-    console.log("Escrow Authority PDA:", escrowAuthority.toBase58());
-
-    // Escrow NFT ATA (reuse from lock test)
     const escrowNftAccount = await getAssociatedTokenAddress(
       positionNftMint.publicKey,
       escrowAuthority,
@@ -576,20 +1006,7 @@ describe("liquidity-locking-program", () => {
       TOKEN_2022_PROGRAM_ID
     );
 
-    // User's NFT ATA (derive, will be created in instruction if needed)
-    const userNftAccount = await getAssociatedTokenAddress(
-      positionNftMint.publicKey,
-      user,
-      false,
-      TOKEN_2022_PROGRAM_ID
-    );
-
-    // Pool state for vaults/mints (reuse from lock test)
     const poolState = await cpAmm.fetchPoolState(pool);
-    const tokenAVault = poolState.tokenAVault;
-    const tokenBVault = poolState.tokenBVault;
-    const tokenAMint = poolState.tokenAMint;
-    const tokenBMint = poolState.tokenBMint;
 
     const [eventAuthority] = PublicKey.findProgramAddressSync(
       [Buffer.from("__event_authority")],
@@ -600,41 +1017,1872 @@ describe("liquidity-locking-program", () => {
       units: 400_000,
     });
 
-    const tx = await program.methods
-      .unlockLiquidity(liquidityDelta)
-      .accounts({
-        lockAccount, // Reuse from lock test
-        positionNftMint: positionNftMint.publicKey,
-        escrowAuthority,
-        userTokenA,
-        userTokenB,
-        escrowNftAccount,
-        userNftAccount,
-        pool,
-        position: positionAddress, // Use the derived address
-        tokenAVault,
-        tokenBVault,
-        tokenAMint,
-        tokenBMint,
-        eventAuthority,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        token2022Program: TOKEN_2022_PROGRAM_ID,
-        associatedTokenProgram: new PublicKey(
-          "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
-        ),
-        systemProgram: new PublicKey("11111111111111111111111111111111"),
-        dammProgram: METEORA_PROGRAM_ID,
-        user,
-        clock: new PublicKey("SysvarC1ock11111111111111111111111111111111"),
+    // Max tokens willing to deposit on each side (only the required amounts are staged)
+    const maxTokenIn = new BN(10_000_000);
+    const balanceABefore = (await getAccount(connection, userTokenA)).amount;
+    const balanceBBefore = (await getAccount(connection, userTokenB)).amount;
+
+    const increase = (maxIn: BN) =>
+      program.methods
+        .increaseLock(liquidityDelta, maxIn, maxIn)
+        .accounts({
+          lockAccount,
+          positionNftMint: positionNftMint.publicKey,
+          userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+          receiptMint: deriveReceiptMint(lockAccount),
+          escrowNftAccount,
+          userTokenA,
+          userTokenB,
+          pool,
+          position,
+          tokenAVault: poolState.tokenAVault,
+          tokenBVault: poolState.tokenBVault,
+          tokenAMint: poolState.tokenAMint,
+          tokenBMint: poolState.tokenBMint,
+          dammEventAuthority: eventAuthority,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          user,
+        })
+        .preInstructions([computeUnitIx])
+        .signers([userKeypair])
+        .rpc();
+
+    // A zero max-in is below any top-up's deposit, so it is rejected before tokens move
+    await expectError(increase(new BN(0)), "SlippageExceeded");
+
+    const tx = await increase(maxTokenIn);
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(lockAccount);
+    console.log("Increased Lock Account:", lockData);
+
+    // The user paid at most max-in, and nothing was left behind in escrow
+    const spentA = balanceABefore - (await getAccount(connection, userTokenA)).amount;
+    const spentB = balanceBBefore - (await getAccount(connection, userTokenB)).amount;
+    assert.isTrue(spentA <= BigInt(maxTokenIn.toString()));
+    assert.isTrue(spentB <= BigInt(maxTokenIn.toString()));
+    for (const mint of [SLERF_MINT, USDC_MINT]) {
+      const escrowToken = await getAccount(
+        connection,
+        getAssociatedTokenAddressSync(mint, escrowAuthority, true, TOKEN_PROGRAM_ID)
+      );
+      assert.equal(escrowToken.amount.toString(), "0");
+    }
+
+    // The receipt metadata follows the topped-up lock
+    assert.equal(
+      await fetchReceiptField(lockAccount, "liquidity"),
+      lockData.liquidityLocked.toString()
+    );
+    assert.equal(
+      await fetchReceiptField(lockAccount, "lock_end"),
+      lockData.lockEnd.toString()
+    );
+  });
+
+  it("Reject Inactive Pools", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const original = (await connection.getAccountInfo(pool))!.data;
+
+    // Byte offsets in the DAMM v2 Pool account (discriminator included)
+    const ACTIVATION_POINT_OFFSET = 472;
+    const ACTIVATION_TYPE_OFFSET = 480;
+    const POOL_STATUS_OFFSET = 481;
+
+    // Rewrite the pool account in place through the Surfpool cheatcode
+    const setPoolData = (data: Buffer) =>
+      rpcCall("surfnet_setAccount", [
+        pool.toBase58(),
+        { data: data.toString("hex") },
+      ]);
+    const patchPool = (
+      poolStatus: number,
+      activationType: number,
+      activationPoint: bigint
+    ) => {
+      const data = Buffer.from(original);
+      data.writeBigUInt64LE(activationPoint, ACTIVATION_POINT_OFFSET);
+      data.writeUInt8(activationType, ACTIVATION_TYPE_OFFSET);
+      data.writeUInt8(poolStatus, POOL_STATUS_OFFSET);
+      return setPoolData(data);
+    };
+
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+    const userTokenA = getAssociatedTokenAddressSync(SLERF_MINT, user);
+    const userTokenB = getAssociatedTokenAddressSync(USDC_MINT, user);
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+    const maxTokenIn = new BN(10_000_000);
+
+    // A fresh lock on the pool
+    const lock = () => {
+      const nftMint = Keypair.generate();
+      const [newLockAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lock"), nftMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [escrowAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow_authority"), newLockAccount.toBuffer()],
+        program.programId
+      );
+      return program.methods
+        .lockLiquidity(new BN(100), { tier: { 0: 1 } }, maxTokenIn, maxTokenIn, {
+          escrow: {},
+        })
+        .accounts({
+          userTokenA,
+          userTokenB,
+          positionNftMint: nftMint.publicKey,
+          positionNftAccount: derivePositionNftAccount(nftMint.publicKey),
+          escrowNftAccount: getAssociatedTokenAddressSync(
+            nftMint.publicKey,
+            escrowAuthority,
+            true,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          pool,
+          position: derivePositionAddress(nftMint.publicKey),
+          tokenAVault: poolState.tokenAVault,
+          tokenBVault: poolState.tokenBVault,
+          tokenAMint: poolState.tokenAMint,
+          tokenBMint: poolState.tokenBMint,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          vesting: null,
+          receiptMint: deriveReceiptMint(newLockAccount),
+          userReceiptAccount: deriveReceiptAccount(newLockAccount, user),
+          user,
+        })
+        .preInstructions([computeUnitIx])
+        .signers([userKeypair, nftMint])
+        .rpc();
+    };
+
+    // A top-up of the main lock
+    const increase = () => {
+      const [escrowAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+        program.programId
+      );
+      return program.methods
+        .increaseLock(new BN(50), maxTokenIn, maxTokenIn)
+        .accounts({
+          lockAccount,
+          positionNftMint: positionNftMint.publicKey,
+          userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+          receiptMint: deriveReceiptMint(lockAccount),
+          escrowNftAccount: getAssociatedTokenAddressSync(
+            positionNftMint.publicKey,
+            escrowAuthority,
+            true,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          userTokenA,
+          userTokenB,
+          pool,
+          position: derivePositionAddress(positionNftMint.publicKey),
+          tokenAVault: poolState.tokenAVault,
+          tokenBVault: poolState.tokenBVault,
+          tokenAMint: poolState.tokenAMint,
+          tokenBMint: poolState.tokenBMint,
+          dammEventAuthority: eventAuthority,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          user,
+        })
+        .preInstructions([computeUnitIx])
+        .signers([userKeypair])
+        .rpc();
+    };
+
+    const never = BigInt("18446744073709551615"); // u64::MAX activation point
+    try {
+      // Disabled pool
+      await patchPool(1, 0, BigInt(0));
+      await expectError(lock(), "PoolDisabled");
+      await expectError(increase(), "PoolDisabled");
+
+      // Slot-based activation still ahead
+      await patchPool(0, 0, never);
+      await expectError(lock(), "PoolNotActivated");
+      await expectError(increase(), "PoolNotActivated");
+
+      // Timestamp-based activation still ahead
+      await patchPool(0, 1, never);
+      await expectError(lock(), "PoolNotActivated");
+      await expectError(increase(), "PoolNotActivated");
+    } finally {
+      await setPoolData(original);
+    }
+
+    // An admin pause of the pool's locks and top-ups is reported separately
+    const [poolConfigPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pool_config"), pool.toBuffer()],
+      program.programId
+    );
+    const flags = { locks: true, topUps: true, claims: false, unlocks: false };
+    await program.methods
+      .pause(flags)
+      .accounts({ poolConfig: poolConfigPda, authority: admin })
+      .signers([adminKeypair])
+      .rpc();
+    try {
+      await expectError(lock(), "PoolPaused");
+      await expectError(increase(), "PoolPaused");
+    } finally {
+      await program.methods
+        .unpause(flags)
+        .accounts({ poolConfig: poolConfigPda, admin })
+        .signers([adminKeypair])
+        .rpc();
+    }
+  });
+
+  it("Lock Token-2022 Pool Tokens", async () => {
+    // A Token-2022 mint with a 1% transfer fee, paired with SPL USDC
+    // in a fresh DAMM v2 pool
+    const feeMint = Keypair.generate();
+    const decimals = 6;
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const createMintTx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: user,
+        newAccountPubkey: feeMint.publicKey,
+        space: mintLen,
+        lamports: await connection.getMinimumBalanceForRentExemption(mintLen),
+        programId: TOKEN_2022_PROGRAM_ID,
+      }),
+      createInitializeTransferFeeConfigInstruction(
+        feeMint.publicKey,
+        user,
+        user,
+        100, // 1%
+        BigInt(10 ** 12),
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createInitializeMintInstruction(
+        feeMint.publicKey,
+        decimals,
+        user,
+        null,
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await provider.sendAndConfirm(createMintTx, [userKeypair, feeMint]);
+
+    const userFeeAccount = getAssociatedTokenAddressSync(
+      feeMint.publicKey,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const fundTx = new Transaction().add(
+      createAssociatedTokenAccountInstruction(
+        user,
+        userFeeAccount,
+        user,
+        feeMint.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      createMintToInstruction(
+        feeMint.publicKey,
+        userFeeAccount,
+        user,
+        BigInt(1_000_000 * 10 ** decimals),
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+    await provider.sendAndConfirm(fundTx, [userKeypair]);
+
+    // Full-range pool at price 1
+    const sqrtMinPrice = new BN("4295048016");
+    const sqrtMaxPrice = new BN("79226673521066979257578248091");
+    const initSqrtPrice = new BN(1).shln(64);
+    const seedAmount = new BN(100 * 10 ** decimals);
+    const creatorPositionNft = Keypair.generate();
+    const { tx: createPoolTx, pool } = await cpAmm.createCustomPool({
+      payer: user,
+      creator: user,
+      positionNft: creatorPositionNft.publicKey,
+      tokenAMint: feeMint.publicKey,
+      tokenBMint: USDC_MINT,
+      tokenAAmount: seedAmount,
+      tokenBAmount: seedAmount,
+      sqrtMinPrice,
+      sqrtMaxPrice,
+      initSqrtPrice,
+      liquidityDelta: cpAmm.getLiquidityDelta({
+        maxAmountTokenA: seedAmount,
+        maxAmountTokenB: seedAmount,
+        sqrtPrice: initSqrtPrice,
+        sqrtMinPrice,
+        sqrtMaxPrice,
+      }),
+      poolFees: {
+        baseFee: {
+          cliffFeeNumerator: new BN(2_500_000), // 0.25%
+          firstFactor: 0,
+          secondFactor: Array(8).fill(0),
+          thirdFactor: new BN(0),
+          baseFeeMode: 0,
+        },
+        padding: [],
+        dynamicFee: null,
+      },
+      hasAlphaVault: false,
+      activationType: 1, // Timestamp
+      collectFeeMode: 0,
+      activationPoint: null,
+      tokenAProgram: TOKEN_2022_PROGRAM_ID,
+      tokenBProgram: TOKEN_PROGRAM_ID,
+    });
+    await provider.sendAndConfirm(createPoolTx, [
+      userKeypair,
+      creatorPositionNft,
+    ]);
+
+    const oneMonth = new BN(30 * 24 * 60 * 60);
+    const labelBytes = Buffer.alloc(16);
+    labelBytes.write("1M");
+    const poolConfigTx = await program.methods
+      .initializePoolConfig(
+        [
+          {
+            durationSeconds: oneMonth,
+            label: Array.from(labelBytes),
+            rewardMultiplierBps: 10_000,
+            enabled: true,
+            earlyUnlockPenaltyBps: 1_000,
+          },
+        ],
+        oneMonth,
+        oneMonth,
+        null,
+        null
+      )
+      .accounts({ pool, admin })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(poolConfigTx);
+
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const userTokenB = getAssociatedTokenAddressSync(USDC_MINT, user);
+    // About one token per side at price 1
+    const liquidityDelta = new BN(10 ** decimals).shln(64);
+    const lock = (tokenAProgram: PublicKey) => {
+      const nftMint = Keypair.generate();
+      const [newLockAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from("lock"), nftMint.publicKey.toBuffer()],
+        program.programId
+      );
+      const [escrowAuthority] = PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow_authority"), newLockAccount.toBuffer()],
+        program.programId
+      );
+      const maxTokenIn = new BN(10_000_000);
+      return {
+        lockAccount: newLockAccount,
+        send: () =>
+          program.methods
+            .lockLiquidity(
+              liquidityDelta,
+              { tier: { 0: 0 } },
+              maxTokenIn,
+              maxTokenIn,
+              { escrow: {} }
+            )
+            .accounts({
+              userTokenA: userFeeAccount,
+              userTokenB,
+              positionNftMint: nftMint.publicKey,
+              positionNftAccount: derivePositionNftAccount(nftMint.publicKey),
+              escrowNftAccount: getAssociatedTokenAddressSync(
+                nftMint.publicKey,
+                escrowAuthority,
+                true,
+                TOKEN_2022_PROGRAM_ID
+              ),
+              pool,
+              position: derivePositionAddress(nftMint.publicKey),
+              tokenAVault: poolState.tokenAVault,
+              tokenBVault: poolState.tokenBVault,
+              tokenAMint: poolState.tokenAMint,
+              tokenBMint: poolState.tokenBMint,
+              tokenAProgram,
+              tokenBProgram: TOKEN_PROGRAM_ID,
+              vesting: null,
+              receiptMint: deriveReceiptMint(newLockAccount),
+              userReceiptAccount: deriveReceiptAccount(newLockAccount, user),
+              user,
+            })
+            .preInstructions([
+              ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+            ])
+            .signers([userKeypair, nftMint])
+            .rpc(),
+      };
+    };
+
+    // Each side is validated against its own token program
+    await expectError(
+      lock(TOKEN_PROGRAM_ID).send(),
+      "ConstraintTokenTokenProgram"
+    );
+
+    // Token-2022 (transfer-fee) token A, SPL token B
+    const feeBalance = async () =>
+      (
+        await getAccount(
+          connection,
+          userFeeAccount,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).amount;
+    const balanceBefore = await feeBalance();
+    const mixed = lock(TOKEN_2022_PROGRAM_ID);
+    logTxnSignature(await mixed.send());
+
+    const lockData = await program.account.lockAccount.fetch(mixed.lockAccount);
+    assert.equal(
+      lockData.liquidityLocked.toString(),
+      liquidityDelta.toString()
+    );
+    assert.isTrue(
+      (await feeBalance()) < balanceBefore,
+      "No Token-2022 deposit"
+    );
+  });
+
+  it("Extend Lock", async () => {
+    const duration = { tier: { 0: 2 } }; // Upgrade to the 6 month tier
+
+    const tx = await program.methods
+      .extendLock(duration)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+        receiptMint: deriveReceiptMint(lockAccount),
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(lockAccount);
+    console.log("Extended Lock Account:", lockData);
+
+    // The receipt metadata carries the new lock_end
+    assert.equal(
+      await fetchReceiptField(lockAccount, "lock_end"),
+      lockData.lockEnd.toString()
+    );
+  });
+
+  it("Claim Locked Fees", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+
+    const position = derivePositionAddress(positionNftMint.publicKey);
+
+    // User's token ATAs (receive the harvested fees)
+    const userTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    // Treasury token ATAs (receive the protocol fee)
+    const treasuryTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const treasuryTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    // Escrow Authority PDA
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+
+    // Escrow NFT ATA (holds the position NFT)
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      positionNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // Pool state for vaults/mints
+    const poolState = await cpAmm.fetchPoolState(pool);
+
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+
+    const tx = await program.methods
+      .claimLockedFees()
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+        escrowNftAccount,
+        userTokenA,
+        userTokenB,
+        treasuryTokenA,
+        treasuryTokenB,
+        pool,
+        position,
+        tokenAVault: poolState.tokenAVault,
+        tokenBVault: poolState.tokenBVault,
+        tokenAMint: poolState.tokenAMint,
+        tokenBMint: poolState.tokenBMint,
+        dammEventAuthority: eventAuthority,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        user,
+      })
+      .preInstructions([computeUnitIx])
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    // Lock stays active after harvesting fees
+    const lockData = await program.account.lockAccount.fetch(lockAccount);
+    console.log("Lock status after fee claim:", lockData.status);
+  });
+
+  it("Reject Mismatched Meteora Accounts", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const poolState = await cpAmm.fetchPoolState(pool);
+
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+    const userTokenA = getAssociatedTokenAddressSync(SLERF_MINT, user);
+    const userTokenB = getAssociatedTokenAddressSync(USDC_MINT, user);
+    const treasuryTokenA = getAssociatedTokenAddressSync(SLERF_MINT, admin);
+    const treasuryTokenB = getAssociatedTokenAddressSync(USDC_MINT, admin);
+
+    // claim_locked_fees on the main lock, with some Meteora accounts swapped out
+    const claimFees = (overrides: Record<string, PublicKey>) =>
+      program.methods
+        .claimLockedFees()
+        .accounts({
+          lockAccount,
+          positionNftMint: positionNftMint.publicKey,
+          userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+          escrowNftAccount: getAssociatedTokenAddressSync(
+            positionNftMint.publicKey,
+            escrowAuthority,
+            true,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          userTokenA,
+          userTokenB,
+          treasuryTokenA,
+          treasuryTokenB,
+          pool,
+          position: derivePositionAddress(positionNftMint.publicKey),
+          tokenAVault: poolState.tokenAVault,
+          tokenBVault: poolState.tokenBVault,
+          tokenAMint: poolState.tokenAMint,
+          tokenBMint: poolState.tokenBMint,
+          dammEventAuthority: eventAuthority,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          user,
+          ...overrides,
+        })
+        .signers([userKeypair])
+        .rpc();
+
+    // Vaults swapped between the two sides
+    await expectError(
+      claimFees({
+        tokenAVault: poolState.tokenBVault,
+        tokenBVault: poolState.tokenAVault,
+      }),
+      "InvalidVault"
+    );
+
+    // Mints swapped (with matching user and treasury accounts, so only the pool check can catch it)
+    await expectError(
+      claimFees({
+        tokenAMint: poolState.tokenBMint,
+        tokenBMint: poolState.tokenAMint,
+        userTokenA: userTokenB,
+        userTokenB: userTokenA,
+        treasuryTokenA: treasuryTokenB,
+        treasuryTokenB: treasuryTokenA,
+      }),
+      "InvalidMint"
+    );
+
+    // An account that is not a Meteora pool
+    await expectError(
+      claimFees({ pool: Keypair.generate().publicKey }),
+      "InvalidPool"
+    );
+
+    // A position other than the one recorded on the lock
+    await expectError(
+      claimFees({
+        position: derivePositionAddress(Keypair.generate().publicKey),
+      }),
+      "InvalidPosition"
+    );
+  });
+
+  it("Claim Locked Rewards", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const rewardIndex = 0;
+
+    // Let some rewards accrue and vest
+    await skipTime(7 * 24 * 60 * 60);
+
+    const position = derivePositionAddress(positionNftMint.publicKey);
+
+    // Escrow Authority PDA
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+
+    // Escrow NFT ATA (holds the position NFT)
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      positionNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // Reward vault from Meteora pool state
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const rewardVault = poolState.rewardInfos[rewardIndex].vault;
+
+    // Escrow and user reward (SLERF) accounts
+    const escrowRewardAccount = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      escrowAuthority,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const userRewardAccount = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const treasuryRewardAccount = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+
+    const tx = await program.methods
+      .claimLockedRewards(rewardIndex)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+        escrowNftAccount,
+        escrowRewardAccount,
+        userRewardAccount,
+        treasuryRewardAccount,
+        rewardMint: SLERF_MINT,
+        pool,
+        position,
+        rewardVault,
+        dammEventAuthority: eventAuthority,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        user,
+      })
+      .preInstructions([computeUnitIx])
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(lockAccount);
+    console.log(
+      "Rewards earned / claimed:",
+      lockData.totalRewardsEarned.toString(),
+      lockData.rewardsClaimed.toString()
+    );
+  });
+
+  it("Extend Lock Keeps Vested Rewards", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const rewardIndex = 0;
+    const position = derivePositionAddress(positionNftMint.publicKey);
+
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+    const poolState = await cpAmm.fetchPoolState(pool);
+
+    const claim = async () =>
+      program.methods
+        .claimLockedRewards(rewardIndex)
+        .accounts({
+          lockAccount,
+          positionNftMint: positionNftMint.publicKey,
+          userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+          escrowNftAccount: await getAssociatedTokenAddress(
+            positionNftMint.publicKey,
+            escrowAuthority,
+            true,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          escrowRewardAccount: await getAssociatedTokenAddress(
+            SLERF_MINT,
+            escrowAuthority,
+            true,
+            TOKEN_PROGRAM_ID
+          ),
+          userRewardAccount: await getAssociatedTokenAddress(
+            SLERF_MINT,
+            user,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          treasuryRewardAccount: await getAssociatedTokenAddress(
+            SLERF_MINT,
+            admin,
+            false,
+            TOKEN_PROGRAM_ID
+          ),
+          rewardMint: SLERF_MINT,
+          pool,
+          position,
+          rewardVault: poolState.rewardInfos[rewardIndex].vault,
+          dammEventAuthority: eventAuthority,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          user,
+        })
+        .preInstructions([
+          ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+        ])
+        .signers([userKeypair])
+        .rpc();
+
+    // Claim what has vested so far, then move to the 12 month tier
+    await skipTime(7 * 24 * 60 * 60);
+    logTxnSignature(await claim());
+    const beforeExtend = await program.account.lockAccount.fetch(lockAccount);
+
+    const tx = await program.methods
+      .extendLock({ tier: { 0: 3 } })
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+        receiptMint: deriveReceiptMint(lockAccount),
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+    logTxnSignature(tx);
+
+    // The new window starts now; everything claimed stays vested
+    const afterExtend = await program.account.lockAccount.fetch(lockAccount);
+    assert.isTrue(afterExtend.lockStart.gt(beforeExtend.lockStart));
+    assert.isTrue(afterExtend.lockEnd.gt(beforeExtend.lockEnd));
+    assert.isTrue(
+      afterExtend.rewardsVestedBase.gte(beforeExtend.rewardsClaimed)
+    );
+
+    // Rewards keep vesting over the new window without clawing back what was released
+    await skipTime(7 * 24 * 60 * 60);
+    logTxnSignature(await claim());
+
+    const afterClaim = await program.account.lockAccount.fetch(lockAccount);
+    assert.isTrue(afterClaim.rewardsClaimed.gte(beforeExtend.rewardsClaimed));
+    assert.isTrue(afterClaim.rewardsClaimed.lte(afterClaim.totalRewardsEarned));
+  });
+
+  it("Claim Incentives", async () => {
+    // The main lock has been accruing since Fund Incentives
+    const userIncentiveAccount = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const tx = await program.methods
+      .claimIncentives()
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        userReceiptAccount: deriveReceiptAccount(lockAccount, user),
+        userIncentiveAccount,
+        incentiveMint: SLERF_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(lockAccount);
+    console.log(
+      "Incentive checkpoint:",
+      Buffer.from(lockData.incentiveCheckpoint).toString("hex")
+    );
+  });
+
+  it("Transfer Lock With Receipt", async () => {
+    // Move the main lock's receipt to the admin wallet
+    const receiptMint = deriveReceiptMint(lockAccount);
+    const userReceiptAccount = deriveReceiptAccount(lockAccount, user);
+    const adminReceiptAccount = deriveReceiptAccount(lockAccount, admin);
+
+    const tx = await program.methods
+      .transferLock(admin)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        receiptMint,
+        userReceiptAccount,
+        newOwnerAccount: admin,
+        newOwnerReceiptAccount: adminReceiptAccount,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    // The previous owner no longer holds the receipt and is rejected
+    await expectError(
+      program.methods
+        .transferLock(user)
+        .accounts({
+          lockAccount,
+          positionNftMint: positionNftMint.publicKey,
+          receiptMint,
+          userReceiptAccount,
+          newOwnerAccount: user,
+          newOwnerReceiptAccount: userReceiptAccount,
+          user,
+        })
+        .signers([userKeypair])
+        .rpc(),
+      "Unauthorized"
+    );
+
+    // The receipt holder hands it back for the remaining tests
+    const returnTx = await program.methods
+      .transferLock(user)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        receiptMint,
+        userReceiptAccount: adminReceiptAccount,
+        newOwnerAccount: user,
+        newOwnerReceiptAccount: userReceiptAccount,
+        user: admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+
+    logTxnSignature(returnTx);
+
+    const userReceipt = await getAccount(
+      connection,
+      userReceiptAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(userReceipt.amount.toString(), "1");
+  });
+
+  it("Partial Unlock", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+
+    // Skip time to expire the lock (12 months in seconds, after Extend Lock Keeps Vested Rewards)
+    await skipTime(12 * 30 * 24 * 60 * 60);
+
+    // Withdraw half of the liquidity; the rest stays locked
+    const lockBefore = await program.account.lockAccount.fetch(lockAccount);
+    const liquidityDelta = lockBefore.liquidityLocked.div(new BN(2));
+
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      positionNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const tx = await program.methods
+      .unlockLiquidity(liquidityDelta, new BN(0), new BN(0))
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        escrowAuthority,
+        userTokenA: await getAssociatedTokenAddress(SLERF_MINT, user),
+        userTokenB: await getAssociatedTokenAddress(USDC_MINT, user),
+        treasuryTokenA: await getAssociatedTokenAddress(SLERF_MINT, admin),
+        treasuryTokenB: await getAssociatedTokenAddress(USDC_MINT, admin),
+        escrowNftAccount,
+        userNftAccount: await getAssociatedTokenAddress(
+          positionNftMint.publicKey,
+          user,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        pool,
+        position: derivePositionAddress(positionNftMint.publicKey),
+        tokenAVault: poolState.tokenAVault,
+        tokenBVault: poolState.tokenBVault,
+        tokenAMint: poolState.tokenAMint,
+        tokenBMint: poolState.tokenBMint,
+        dammEventAuthority: eventAuthority,
+        vesting: null, // Escrow lock
+        receiptMint,
+        userReceiptAccount: await getAssociatedTokenAddress(
+          receiptMint,
+          user,
+          false,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        dammProgram: METEORA_PROGRAM_ID,
+        user,
+      })
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+      ])
+      .signers([userKeypair])
+      .rpc();
+    logTxnSignature(tx);
+
+    // The NFT stays in escrow and the lock stays active with the remainder
+    const escrowNft = await getAccount(
+      connection,
+      escrowNftAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.equal(escrowNft.amount.toString(), "1");
+    const lockAfter = await program.account.lockAccount.fetch(lockAccount);
+    assert.deepEqual(lockAfter.status, { active: {} });
+    assert.equal(
+      lockAfter.liquidityLocked.toString(),
+      lockBefore.liquidityLocked.sub(liquidityDelta).toString()
+    );
+    assert.equal(
+      await fetchReceiptField(lockAccount, "liquidity"),
+      lockAfter.liquidityLocked.toString()
+    );
+    // The lock has expired, so it only counts at 1x for incentives from here on
+    assert.equal(lockAfter.rewardMultiplierBps, 10_000);
+  });
+
+  it("Unlock Liquidity", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const liquidityDelta = new BN(0); // 0 for full unlock (the remainder after Partial Unlock)
+
+    // Derive accounts (reuse from lock test)
+    const positionAddress = derivePositionAddress(positionNftMint.publicKey);
+    console.log("Derived Position Address:", positionAddress.toBase58());
+    const positionNftAccount = derivePositionNftAccount(
+      positionNftMint.publicKey
+    );
+    console.log("Derived Position NFT Account:", positionNftAccount.toBase58());
+
+    // Debug: Check pool and position discriminators
+    const poolInfo = await program.provider.connection.getAccountInfo(pool);
+    if (poolInfo) {
+      console.log("Pool owner:", poolInfo.owner.toBase58());
+      console.log("Pool discriminator:", poolInfo.data.slice(0, 8));
+    } else {
+      console.log("Pool not found");
+    }
+
+    const positionInfo = await program.provider.connection.getAccountInfo(
+      positionAddress
+    );
+    if (positionInfo) {
+      console.log("Position owner:", positionInfo.owner.toBase58());
+      console.log("Position discriminator:", positionInfo.data.slice(0, 8));
+    } else {
+      console.log("Position not found");
+    }
+
+    // User's token ATAs (reuse from lock test)
+    const userTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    // Treasury token ATAs (receive the protocol fee)
+    const treasuryTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const treasuryTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    // Escrow Authority PDA (reuse from lock test)
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+    console.log("Escrow Authority PDA:", escrowAuthority.toBase58());
+
+    // Escrow NFT ATA (reuse from lock test)
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      positionNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // User's NFT ATA (derive, will be created in instruction if needed)
+    const userNftAccount = await getAssociatedTokenAddress(
+      positionNftMint.publicKey,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // Receipt NFT held by the user (burned on full unlock)
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const userReceiptAccount = await getAssociatedTokenAddress(
+      receiptMint,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    // Pool state for vaults/mints (reuse from lock test)
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const tokenAVault = poolState.tokenAVault;
+    const tokenBVault = poolState.tokenBVault;
+    const tokenAMint = poolState.tokenAMint;
+    const tokenBMint = poolState.tokenBMint;
+
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+
+    // Min tokens to receive on each side (after protocol fee)
+    const minTokenOut = new BN(0);
+
+    const unlock = (minOut: BN) =>
+      program.methods
+        .unlockLiquidity(liquidityDelta, minOut, minOut)
+        .accounts({
+          lockAccount, // Reuse from lock test
+          positionNftMint: positionNftMint.publicKey,
+          escrowAuthority,
+          userTokenA,
+          userTokenB,
+          treasuryTokenA,
+          treasuryTokenB,
+          escrowNftAccount,
+          userNftAccount,
+          pool,
+          position: positionAddress, // Use the derived address
+          tokenAVault,
+          tokenBVault,
+          tokenAMint,
+          tokenBMint,
+          dammEventAuthority: eventAuthority,
+          vesting: null, // Escrow lock
+          receiptMint,
+          userReceiptAccount,
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          associatedTokenProgram: new PublicKey(
+            "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+          ),
+          systemProgram: new PublicKey("11111111111111111111111111111111"),
+          dammProgram: METEORA_PROGRAM_ID,
+          user,
+          clock: new PublicKey("SysvarC1ock11111111111111111111111111111111"),
+        })
+        .preInstructions([computeUnitIx])
+        .signers([userKeypair])
+        .rpc();
+
+    // A min-out above what the liquidity returns is rejected by the forwarded threshold
+    await expectError(
+      unlock(new BN("18446744073709551615")),
+      "ExceededSlippage"
+    );
+
+    const tx = await unlock(minTokenOut);
+    logTxnSignature(tx);
+
+    // Fetch and log updated lock account
+    const lockData = await program.account.lockAccount.fetch(lockAccount);
+    console.log("Updated Lock Account:", lockData);
+  });
+
+  it("Lock Existing Position", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const duration = { tier: { 0: 0 } }; // 1 month tier
+
+    // Position the user already holds (created and funded outside the lock)
+    existingNftMint = await createFundedPosition(pool, new BN(100));
+    const positionNftAccount = derivePositionNftAccount(
+      existingNftMint.publicKey
+    );
+
+    // Escrow the existing NFT and lock all of its unlocked liquidity
+    const [existingLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), existingNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), existingLockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      existingNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await program.methods
+      .lockExistingPosition(duration)
+      .accounts({
+        positionNftMint: existingNftMint.publicKey,
+        positionNftAccount,
+        escrowNftAccount,
+        receiptMint: deriveReceiptMint(existingLockAccount),
+        userReceiptAccount: deriveReceiptAccount(existingLockAccount, user),
+        pool,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(
+      existingLockAccount
+    );
+    console.log("Existing Position Lock Account:", lockData);
+
+    // Existing positions get a receipt like fresh locks
+    assert.isTrue(
+      lockData.receiptMint.equals(deriveReceiptMint(existingLockAccount))
+    );
+    assert.equal(
+      await fetchReceiptField(existingLockAccount, "liquidity"),
+      lockData.liquidityLocked.toString()
+    );
+  });
+
+  it("Emergency Withdraw", async () => {
+    // Admin declares Meteora compromised, the user pulls the NFT before lock_end
+    const emergencyTx = await program.methods
+      .setEmergencyMode(true)
+      .accounts({
+        admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(emergencyTx);
+
+    const [existingLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), existingNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), existingLockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      existingNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const userNftAccount = await getAssociatedTokenAddress(
+      existingNftMint.publicKey,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const tx = await program.methods
+      .emergencyWithdraw()
+      .accounts({
+        lockAccount: existingLockAccount,
+        positionNftMint: existingNftMint.publicKey,
+        escrowNftAccount,
+        userNftAccount,
+        receiptMint: deriveReceiptMint(existingLockAccount),
+        userReceiptAccount: deriveReceiptAccount(existingLockAccount, user),
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+    logTxnSignature(tx);
+
+    const restoreTx = await program.methods
+      .setEmergencyMode(false)
+      .accounts({
+        admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+    logTxnSignature(restoreTx);
+
+    const lockData = await program.account.lockAccount.fetch(
+      existingLockAccount
+    );
+    console.log("Emergency Withdrawn Lock Account:", lockData);
+  });
+
+  it("Lock Liquidity (Native Vesting)", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const liquidityDelta = new BN(3_000); // Enough to split into daily periods
+    const duration = { tier: { 0: 0 } }; // 1 month tier, released daily by Meteora
+
+    const vestingNftMint = Keypair.generate();
+    const position = derivePositionAddress(vestingNftMint.publicKey);
+    const positionNftAccount = derivePositionNftAccount(
+      vestingNftMint.publicKey
+    );
+
+    const [vestingLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), vestingNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [vesting] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock_vesting"), vestingLockAccount.toBuffer()],
+      program.programId
+    );
+    const [receiptMint] = PublicKey.findProgramAddressSync(
+      [Buffer.from("receipt_mint"), vestingLockAccount.toBuffer()],
+      program.programId
+    );
+    const userReceiptAccount = await getAssociatedTokenAddress(
+      receiptMint,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), vestingLockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      vestingNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const userTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const poolState = await cpAmm.fetchPoolState(pool);
+
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 600_000,
+    });
+    const maxTokenIn = new BN(10_000_000);
+
+    const tx = await program.methods
+      .lockLiquidity(liquidityDelta, duration, maxTokenIn, maxTokenIn, {
+        nativeVesting: {},
+      })
+      .accounts({
+        userTokenA,
+        userTokenB,
+        positionNftMint: vestingNftMint.publicKey,
+        positionNftAccount,
+        escrowNftAccount,
+        vesting,
+        receiptMint,
+        userReceiptAccount,
+        pool,
+        position,
+        tokenAVault: poolState.tokenAVault,
+        tokenBVault: poolState.tokenBVault,
+        tokenAMint: poolState.tokenAMint,
+        tokenBMint: poolState.tokenBMint,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        user,
+      })
+      .preInstructions([computeUnitIx])
+      .signers([userKeypair, vestingNftMint])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    // Skip a few days so Meteora releases part of the schedule
+    await skipTime(5 * 24 * 60 * 60);
+
+    // Treasury (admin) token accounts
+    const treasuryTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const treasuryTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userNftAccount = await getAssociatedTokenAddress(
+      vestingNftMint.publicKey,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    // Withdraw everything released so far (0 = all released liquidity)
+    const unlockTx = await program.methods
+      .unlockLiquidity(new BN(0), new BN(0), new BN(0))
+      .accounts({
+        lockAccount: vestingLockAccount,
+        positionNftMint: vestingNftMint.publicKey,
+        escrowAuthority,
+        userTokenA,
+        userTokenB,
+        treasuryTokenA,
+        treasuryTokenB,
+        escrowNftAccount,
+        userNftAccount,
+        pool,
+        position,
+        tokenAVault: poolState.tokenAVault,
+        tokenBVault: poolState.tokenBVault,
+        tokenAMint: poolState.tokenAMint,
+        tokenBMint: poolState.tokenBMint,
+        vesting,
+        receiptMint,
+        userReceiptAccount,
+        dammEventAuthority: eventAuthority,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        token2022Program: TOKEN_2022_PROGRAM_ID,
+        dammProgram: METEORA_PROGRAM_ID,
+        user,
       })
       .preInstructions([computeUnitIx])
       .signers([userKeypair])
       .rpc();
 
+    logTxnSignature(unlockTx);
+
+    const lockData = await program.account.lockAccount.fetch(
+      vestingLockAccount
+    );
+    console.log("Native Vesting Lock Account:", lockData);
+  });
+
+  it("Lock Forever", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+
+    // Realistic liquidity, so penalty shares only survive a high-precision accumulator
+    permanentNftMint = await createFundedPosition(
+      pool,
+      await liquidityForDeposit(pool, new BN(1_000_000))
+    );
+    const positionNftAccount = derivePositionNftAccount(
+      permanentNftMint.publicKey
+    );
+
+    const [permanentLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), permanentNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), permanentLockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      permanentNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const tx = await program.methods
+      .lockForever()
+      .accounts({
+        positionNftMint: permanentNftMint.publicKey,
+        positionNftAccount,
+        escrowNftAccount,
+        receiptMint: deriveReceiptMint(permanentLockAccount),
+        userReceiptAccount: deriveReceiptAccount(permanentLockAccount, user),
+        pool,
+        dammEventAuthority: eventAuthority,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
     logTxnSignature(tx);
 
-    // Fetch and log updated lock account
+    const lockData = await program.account.lockAccount.fetch(
+      permanentLockAccount
+    );
+    console.log("Permanent Lock Account:", lockData);
+
+    // Emergency mode releases escrowed NFTs, but never a permanent lock's
+    const setEmergency = (enabled: boolean) =>
+      program.methods
+        .setEmergencyMode(enabled)
+        .accounts({ admin })
+        .signers([adminKeypair])
+        .rpc();
+    await setEmergency(true);
+    try {
+      await expectError(
+        program.methods
+          .emergencyWithdraw()
+          .accounts({
+            lockAccount: permanentLockAccount,
+            positionNftMint: permanentNftMint.publicKey,
+            escrowNftAccount,
+            userNftAccount: await getAssociatedTokenAddress(
+              permanentNftMint.publicKey,
+              user,
+              false,
+              TOKEN_2022_PROGRAM_ID
+            ),
+            receiptMint: deriveReceiptMint(permanentLockAccount),
+            userReceiptAccount: deriveReceiptAccount(permanentLockAccount, user),
+            user,
+          })
+          .signers([userKeypair])
+          .rpc(),
+        "UnsupportedLockMode"
+      );
+    } finally {
+      await setEmergency(false);
+    }
+  });
+
+  it("Early Unlock", async () => {
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const duration = { tier: { 0: 1 } }; // 3 month tier, 20% penalty at the start
+
+    // Lock a fresh position and exit early; the permanent lock above shares the penalty
+    const earlyNftMint = await createFundedPosition(
+      pool,
+      await liquidityForDeposit(pool, new BN(1_000_000))
+    );
+    const positionAddress = derivePositionAddress(earlyNftMint.publicKey);
+    const positionNftAccount = derivePositionNftAccount(earlyNftMint.publicKey);
+
+    const [earlyLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), earlyNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), earlyLockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNftAccount = await getAssociatedTokenAddress(
+      earlyNftMint.publicKey,
+      escrowAuthority,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const lockTx = await program.methods
+      .lockExistingPosition(duration)
+      .accounts({
+        positionNftMint: earlyNftMint.publicKey,
+        positionNftAccount,
+        escrowNftAccount,
+        receiptMint: deriveReceiptMint(earlyLockAccount),
+        userReceiptAccount: deriveReceiptAccount(earlyLockAccount, user),
+        pool,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+    logTxnSignature(lockTx);
+
+    // Each lock escrows its NFT under its own authority, never the permanent lock's
+    const [permanentLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), permanentNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const [permanentEscrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), permanentLockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowNft = await getAccount(
+      connection,
+      escrowNftAccount,
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    assert.isTrue(escrowNft.owner.equals(escrowAuthority));
+    assert.isFalse(escrowAuthority.equals(permanentEscrowAuthority));
+    assert.equal(escrowNft.amount.toString(), "1");
+
+    // Skip a third of the lock: the penalty scales down with the remaining time
+    await skipTime(30 * 24 * 60 * 60);
+
+    const userTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    // Treasury token ATAs (receive the penalty and protocol fee)
+    const treasuryTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const treasuryTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      admin,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userNftAccount = await getAssociatedTokenAddress(
+      earlyNftMint.publicKey,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    const poolState = await cpAmm.fetchPoolState(pool);
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    const computeUnitIx = ComputeBudgetProgram.setComputeUnitLimit({
+      units: 400_000,
+    });
+
+    // Min tokens to keep on each side (after penalty and protocol fee)
+    const minTokenOut = new BN(0);
+
+    const earlyUnlock = (authority: PublicKey) =>
+      program.methods
+        .earlyUnlock(minTokenOut, minTokenOut)
+        .accounts({
+          lockAccount: earlyLockAccount,
+          positionNftMint: earlyNftMint.publicKey,
+          escrowAuthority: authority,
+          userTokenA,
+          userTokenB,
+          treasuryTokenA,
+          treasuryTokenB,
+          escrowNftAccount,
+          userNftAccount,
+          pool,
+          position: positionAddress,
+          tokenAVault: poolState.tokenAVault,
+          tokenBVault: poolState.tokenBVault,
+          tokenAMint: poolState.tokenAMint,
+          tokenBMint: poolState.tokenBMint,
+          dammEventAuthority: eventAuthority,
+          receiptMint: deriveReceiptMint(earlyLockAccount),
+          userReceiptAccount: deriveReceiptAccount(earlyLockAccount, user),
+          tokenAProgram: TOKEN_PROGRAM_ID,
+          tokenBProgram: TOKEN_PROGRAM_ID,
+          token2022Program: TOKEN_2022_PROGRAM_ID,
+          dammProgram: METEORA_PROGRAM_ID,
+          user,
+        })
+        .preInstructions([computeUnitIx])
+        .signers([userKeypair])
+        .rpc();
+
+    // Another lock's escrow authority cannot sign for this lock
+    await expectError(earlyUnlock(permanentEscrowAuthority), "ConstraintSeeds");
+
+    const tx = await earlyUnlock(escrowAuthority);
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(earlyLockAccount);
+    console.log("Early Unlocked Lock Account:", lockData);
+  });
+
+  it("Claim Penalty Rewards", async () => {
+    // The permanent lock was active during the early unlock, so it earned a share
+    const [permanentLockAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("lock"), permanentNftMint.publicKey.toBuffer()],
+      program.programId
+    );
+    const userTokenA = await getAssociatedTokenAddress(
+      SLERF_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+    const userTokenB = await getAssociatedTokenAddress(
+      USDC_MINT,
+      user,
+      false,
+      TOKEN_PROGRAM_ID
+    );
+
+    const balanceABefore = (await getAccount(connection, userTokenA)).amount;
+    const balanceBBefore = (await getAccount(connection, userTokenB)).amount;
+
+    const tx = await program.methods
+      .claimPenaltyRewards()
+      .accounts({
+        lockAccount: permanentLockAccount,
+        positionNftMint: permanentNftMint.publicKey,
+        userReceiptAccount: deriveReceiptAccount(permanentLockAccount, user),
+        userTokenA,
+        userTokenB,
+        tokenAMint: SLERF_MINT,
+        tokenBMint: USDC_MINT,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const lockData = await program.account.lockAccount.fetch(
+      permanentLockAccount
+    );
+    console.log(
+      "Penalty checkpoint A:",
+      Buffer.from(lockData.penaltyCheckpointA).toString("hex")
+    );
+
+    // The penalty share must not round away against realistic liquidity
+    const claimedA = (await getAccount(connection, userTokenA)).amount - balanceABefore;
+    const claimedB = (await getAccount(connection, userTokenB)).amount - balanceBBefore;
+    assert.isTrue(claimedA + claimedB > BigInt(0), "No penalty share was paid out");
+  });
+
+  it("Transfer Lock", async () => {
+    // Hand the lock to the admin wallet, then back to the user
+    // (the lock is fully unlocked, so its receipt is already burned)
+    const tx = await program.methods
+      .transferLock(admin)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        receiptMint: null,
+        userReceiptAccount: null,
+        newOwnerAccount: null,
+        newOwnerReceiptAccount: null,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const returnTx = await program.methods
+      .transferLock(user)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        receiptMint: null,
+        userReceiptAccount: null,
+        newOwnerAccount: null,
+        newOwnerReceiptAccount: null,
+        user: admin,
+      })
+      .signers([adminKeypair])
+      .rpc();
+
+    logTxnSignature(returnTx);
+
     const lockData = await program.account.lockAccount.fetch(lockAccount);
-    console.log("Updated Lock Account:", lockData);
+    console.log("Transferred Lock Account owner:", lockData.user.toBase58());
+  });
+
+  it("Close Lock", async () => {
+    // The lock was fully unlocked above; close it along with its empty position
+    const pool = new PublicKey("8yswq8vqEDeTrN2Ez1Bdq2hRekzvFZgMxrdfUKVaNBtQ");
+    const userNftAccount = await getAssociatedTokenAddress(
+      positionNftMint.publicKey,
+      user,
+      false,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const [eventAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("__event_authority")],
+      METEORA_PROGRAM_ID
+    );
+
+    // Escrow token accounts created by the top-up and the reward claims
+    const [escrowAuthority] = PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow_authority"), lockAccount.toBuffer()],
+      program.programId
+    );
+    const escrowTokenA = getAssociatedTokenAddressSync(
+      SLERF_MINT,
+      escrowAuthority,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const escrowTokenB = getAssociatedTokenAddressSync(
+      USDC_MINT,
+      escrowAuthority,
+      true,
+      TOKEN_PROGRAM_ID
+    );
+    const receiptMint = deriveReceiptMint(lockAccount);
+
+    const tx = await program.methods
+      .closeLock(true)
+      .accounts({
+        lockAccount,
+        positionNftMint: positionNftMint.publicKey,
+        escrowRewardAccount: escrowTokenA, // SLERF is also the reward token
+        escrowTokenA: null, // Closed as the reward account above
+        escrowTokenB,
+        receiptMint,
+        userNftAccount,
+        pool,
+        position: derivePositionAddress(positionNftMint.publicKey),
+        dammEventAuthority: eventAuthority,
+        tokenAProgram: TOKEN_PROGRAM_ID,
+        tokenBProgram: TOKEN_PROGRAM_ID,
+        user,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    logTxnSignature(tx);
+
+    const info = await connection.getAccountInfo(lockAccount);
+    console.log("Lock Account closed:", info === null);
+
+    // The escrow accounts and the receipt mint are closed with the lock
+    for (const account of [escrowTokenA, escrowTokenB, receiptMint]) {
+      assert.isNull(await connection.getAccountInfo(account));
+    }
   });
 });